@@ -0,0 +1,183 @@
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::numbers::Interval;
+use crate::math::polynomial::solve_quadratic;
+use crate::math::vector::{Point, Vec3};
+use crate::scene::object::{Hit, Object};
+
+// Closed cone standing upright (along the y axis) on a circular base, the apex is at the top
+pub struct Cone {
+    pub base_center: Point, // Center of the bottom cap
+    pub radius: f32,        // Radius of the bottom cap
+    pub height: f32,        // Distance between the bottom cap and the apex
+    pub material: Box<dyn Material>,
+}
+
+impl Cone {
+    pub fn new(base_center: Point, radius: f32, height: f32, material: Box<dyn Material>) -> Self {
+        Self {
+            base_center,
+            radius,
+            height,
+            material,
+        }
+    }
+}
+
+impl Object for Cone {
+    fn material(&self) -> &dyn Material {
+        &*self.material
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        // Work relative to the base center, the cone's axis is the y axis from 0 to height
+        let o = ray.origin - self.base_center;
+        let d = ray.direction;
+
+        // The cone's radius shrinks linearly from the base to the apex: x² + z² = (k(height - y))²
+        let k = self.radius / self.height;
+        let k2 = k * k;
+
+        // Candidate intersections as (t, outward normal)
+        let mut candidates: Vec<(f32, Vec3)> = Vec::with_capacity(3);
+
+        // Side: substituting the ray into the cone equation gives a quadratic in t
+        let h = self.height - o.y(); // Height below the apex of the ray origin
+        let a = d.x() * d.x() + d.z() * d.z() - k2 * d.y() * d.y();
+        let b = 2. * (o.x() * d.x() + o.z() * d.z() + k2 * h * d.y());
+        let c = o.x() * o.x() + o.z() * o.z() - k2 * h * h;
+
+        let side_solutions = if a != 0. {
+            solve_quadratic([c as f64, b as f64, a as f64])
+        } else if b != 0. {
+            vec![(-c / b) as f64] // Ray parallel to the side of the cone, only one solution
+        } else {
+            vec![]
+        };
+
+        for t in side_solutions {
+            let t = t as f32;
+            let p = o + t * d;
+
+            // The quadratic also describes the mirrored cone above the apex, only keep the real one
+            if (0. ..=self.height).contains(&p.y()) {
+                // Gradient of the cone's implicit surface
+                let radial = (p.x() * p.x() + p.z() * p.z()).sqrt();
+                let outward_normal = Vec3::new(p.x(), radial * k, p.z()).unit();
+                candidates.push((t, outward_normal));
+            }
+        }
+
+        // Base cap: the ray intersects the plane y = 0 within the radius
+        if d.y() != 0. {
+            let t = -o.y() / d.y();
+            let p = o + t * d;
+
+            if p.x() * p.x() + p.z() * p.z() <= self.radius * self.radius {
+                candidates.push((t, Vec3::new(0., -1., 0.)));
+            }
+        }
+
+        let closest = candidates
+            .into_iter()
+            .filter(|(t, _)| within.surrounds(*t))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        match closest {
+            Some((t, outward_normal)) => {
+                ray_debug!("Ray hit cone at t {}", t);
+
+                (true, Some(Hit::new(ray, ray.at(t), outward_normal, t)))
+            }
+            None => (false, None),
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Cone on base {} with radius {} and height {}, material: {}",
+            self.base_center,
+            self.radius,
+            self.height,
+            self.material.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::Color;
+
+    // Cone with a 45° slope: radius 1 at the base (y = 0), apex at y = 1
+    fn cone() -> Cone {
+        Cone::new(
+            Point::origin(),
+            1.,
+            1.,
+            Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    #[test]
+    fn it_is_hit_on_the_side() {
+        let ray = Ray::new(Point::new(0., 0.5, 5.), Vec3::new(0., 0., -1.));
+
+        let (is_hit, hit) = cone().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        let expected_normal = Vec3::new(0., 1., 1.).unit();
+
+        assert!(is_hit);
+        assert!(hit.front_face);
+        assert!((hit.t - 4.5).abs() < 1e-5);
+        assert!((hit.normal - expected_normal).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_is_hit_on_the_base() {
+        let ray = Ray::new(Point::new(0.5, -5., 0.), Vec3::new(0., 1., 0.));
+
+        let (is_hit, hit) = cone().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(hit.front_face);
+        assert!((hit.t - 5.).abs() < 1e-5);
+        assert!((hit.normal - Vec3::new(0., -1., 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_is_hit_from_the_inside() {
+        let ray = Ray::new(Point::new(0., 0.25, 0.), Vec3::new(0., 1., 0.));
+
+        let (is_hit, hit) = cone().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(!hit.front_face);
+        assert!((hit.t - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn it_is_missed() {
+        let cases = [
+            ((0., 0.9, 5.), (0.5, 0., -1.)), // Passes next to the narrow top
+            ((0., 2., 5.), (0., 0., -1.)),   // Passes the mirrored cone above the apex
+            ((0., 0.5, 5.), (0., 0., 1.)),   // Points away from the cone
+        ];
+
+        for (origin, direction) in cases.iter() {
+            let ray = Ray::new(
+                Point::new(origin.0, origin.1, origin.2),
+                Vec3::new(direction.0, direction.1, direction.2),
+            );
+
+            let (is_hit, hit) = cone().hit_by(&ray, Interval::new(0.001, 100.));
+
+            assert!(!is_hit);
+            assert!(hit.is_none());
+        }
+    }
+}
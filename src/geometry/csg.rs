@@ -0,0 +1,286 @@
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::numbers::Interval;
+use crate::math::vector::Vec3;
+use crate::scene::object::{Hit, Object};
+
+// How the volumes of the two operands are combined
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operation {
+    Union,        // Inside either operand
+    Intersection, // Inside both operands
+    Difference,   // Inside the left operand but not inside the right one
+}
+
+impl Operation {
+    fn inside(&self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            Operation::Union => in_left || in_right,
+            Operation::Intersection => in_left && in_right,
+            Operation::Difference => in_left && !in_right,
+        }
+    }
+}
+
+// Constructive solid geometry: a new solid built from two closed objects. Along a ray, each
+// operand is a series of intervals where the ray is inside it. Combining these intervals with the
+// Operation gives the intervals where the ray is inside the combined solid, whose boundaries are
+// the surface of the result.
+//
+// The combined solid has a single material, the operands' own materials are not used.
+pub struct Csg {
+    pub operation: Operation,
+    pub left: Box<dyn Object>,
+    pub right: Box<dyn Object>,
+    pub material: Box<dyn Material>,
+}
+
+// A point where the ray crosses the surface of one of the operands
+struct Crossing {
+    hit: Hit,
+    from_left: bool,
+}
+
+impl Csg {
+    pub fn new(
+        operation: Operation,
+        left: Box<dyn Object>,
+        right: Box<dyn Object>,
+        material: Box<dyn Material>,
+    ) -> Self {
+        Self {
+            operation,
+            left,
+            right,
+            material,
+        }
+    }
+
+    pub fn union(
+        left: Box<dyn Object>,
+        right: Box<dyn Object>,
+        material: Box<dyn Material>,
+    ) -> Self {
+        Self::new(Operation::Union, left, right, material)
+    }
+
+    pub fn intersection(
+        left: Box<dyn Object>,
+        right: Box<dyn Object>,
+        material: Box<dyn Material>,
+    ) -> Self {
+        Self::new(Operation::Intersection, left, right, material)
+    }
+
+    pub fn difference(
+        left: Box<dyn Object>,
+        right: Box<dyn Object>,
+        material: Box<dyn Material>,
+    ) -> Self {
+        Self::new(Operation::Difference, left, right, material)
+    }
+
+    // All crossings of the combined solid's surface along the ray, ordered by t
+    fn boundaries(&self, ray: &Ray) -> Vec<Hit> {
+        let mut crossings: Vec<Crossing> = self
+            .left
+            .hits_along(ray)
+            .into_iter()
+            .map(|hit| Crossing {
+                hit,
+                from_left: true,
+            })
+            .chain(self.right.hits_along(ray).into_iter().map(|hit| Crossing {
+                hit,
+                from_left: false,
+            }))
+            .collect();
+
+        crossings.sort_by(|a, b| a.hit.t.total_cmp(&b.hit.t));
+
+        // Walk along the ray keeping track of which operands it is inside, every crossing where
+        // the ray enters or leaves the combined solid is part of its surface
+        let mut in_left = false;
+        let mut in_right = false;
+        let mut boundaries = vec![];
+
+        for crossing in crossings {
+            let was_inside = self.operation.inside(in_left, in_right);
+
+            // A front face hit means the ray enters the operand, a back face hit means it leaves
+            if crossing.from_left {
+                in_left = crossing.hit.front_face;
+            } else {
+                in_right = crossing.hit.front_face;
+            }
+
+            if was_inside != self.operation.inside(in_left, in_right) {
+                boundaries.push(self.boundary_hit(ray, crossing));
+            }
+        }
+
+        boundaries
+    }
+
+    fn boundary_hit(&self, ray: &Ray, crossing: Crossing) -> Hit {
+        let outward_normal: Vec3 = if self.operation == Operation::Difference && !crossing.from_left
+        {
+            // The right operand is carved out, its inside is the outside of the result
+            -crossing.hit.outward_normal()
+        } else {
+            crossing.hit.outward_normal()
+        };
+
+        Hit::new(ray, crossing.hit.p, outward_normal, crossing.hit.t)
+            .with_uv(crossing.hit.u, crossing.hit.v)
+            .with_tangent(crossing.hit.tangent)
+    }
+}
+
+impl Object for Csg {
+    fn material(&self) -> &dyn Material {
+        &*self.material
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        match self
+            .boundaries(ray)
+            .into_iter()
+            .find(|hit| within.surrounds(hit.t))
+        {
+            Some(hit) => {
+                ray_debug!("Ray hit CSG {:?} at t {}", self.operation, hit.t);
+
+                (true, Some(hit))
+            }
+            None => (false, None),
+        }
+    }
+
+    fn hits_along(&self, ray: &Ray) -> Vec<Hit> {
+        self.boundaries(ray)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "CSG {:?} of ({}) and ({}), material: {}",
+            self.operation,
+            self.left.describe(),
+            self.right.describe(),
+            self.material.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::sphere::Sphere;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::{Color, Point};
+
+    fn material() -> Box<dyn Material> {
+        Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5)))
+    }
+
+    fn sphere(x: f32, radius: f32) -> Box<dyn Object> {
+        Box::new(Sphere::new(Point::new(x, 0., 0.), radius, material()))
+    }
+
+    // Two unit spheres overlapping between x = -0.5 and x = 0.5
+    fn overlapping(operation: Operation) -> Csg {
+        Csg::new(operation, sphere(-0.5, 1.), sphere(0.5, 1.), material())
+    }
+
+    // Ray along the x axis through both sphere centers, from the left
+    fn ray_along_x() -> Ray {
+        Ray::new(Point::new(-5., 0., 0.), Vec3::new(1., 0., 0.))
+    }
+
+    fn crossings(csg: &Csg, ray: &Ray) -> Vec<(f32, bool, Vec3)> {
+        csg.hits_along(ray)
+            .iter()
+            .map(|hit| (ray.at(hit.t).x(), hit.front_face, hit.outward_normal()))
+            .collect()
+    }
+
+    fn assert_crossings(actual: Vec<(f32, bool, Vec3)>, expected: &[(f32, bool, f32)]) {
+        assert_eq!(actual.len(), expected.len(), "crossings: {actual:?}");
+
+        for ((x, front_face, normal), (expected_x, expected_front_face, expected_normal_x)) in
+            actual.iter().zip(expected.iter())
+        {
+            assert!((x - expected_x).abs() < 1e-5, "{x} != {expected_x}");
+            assert_eq!(front_face, expected_front_face);
+            assert!((*normal - Vec3::new(*expected_normal_x, 0., 0.)).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn it_unites_spheres() {
+        // Only the outer surfaces remain, the crossings inside the other sphere disappear
+        assert_crossings(
+            crossings(&overlapping(Operation::Union), &ray_along_x()),
+            &[(-1.5, true, -1.), (1.5, false, 1.)],
+        );
+    }
+
+    #[test]
+    fn it_intersects_spheres() {
+        // A lens: entering through the right sphere's surface, leaving through the left's
+        assert_crossings(
+            crossings(&overlapping(Operation::Intersection), &ray_along_x()),
+            &[(-0.5, true, -1.), (0.5, false, 1.)],
+        );
+    }
+
+    #[test]
+    fn it_subtracts_spheres() {
+        // A bite out of the left sphere: the carved surface faces away from the right sphere's center
+        assert_crossings(
+            crossings(&overlapping(Operation::Difference), &ray_along_x()),
+            &[(-1.5, true, -1.), (-0.5, false, 1.)],
+        );
+    }
+
+    #[test]
+    fn it_hollows_out_a_sphere() {
+        let shell = Csg::difference(sphere(0., 1.), sphere(0., 0.8), material());
+
+        assert_crossings(
+            crossings(&shell, &ray_along_x()),
+            &[
+                (-1., true, -1.),
+                (-0.8, false, 1.), // Leaving the glass into the cavity, outward points into the cavity
+                (0.8, true, -1.),  // Entering the glass again from the cavity
+                (1., false, 1.),
+            ],
+        );
+    }
+
+    #[test]
+    fn it_reports_the_first_hit_within_range() {
+        let shell = Csg::difference(sphere(0., 1.), sphere(0., 0.8), material());
+
+        // From inside the cavity the first hit is the inner surface of the shell, seen from outside
+        let ray = Ray::new(Point::origin(), Vec3::new(1., 0., 0.));
+        let (is_hit, hit) = shell.hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(hit.front_face);
+        assert!((hit.t - 0.8).abs() < 1e-5);
+        assert!((hit.normal - Vec3::new(-1., 0., 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_is_missed_where_the_result_is_empty() {
+        // Spheres side by side without overlapping have no intersection
+        let csg = Csg::intersection(sphere(-2., 1.), sphere(2., 1.), material());
+
+        let (is_hit, hit) = csg.hit_by(&ray_along_x(), Interval::new(0.001, 100.));
+
+        assert!(!is_hit);
+        assert!(hit.is_none());
+    }
+}
@@ -0,0 +1,214 @@
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::numbers::Interval;
+use crate::math::vector::{Point, Vec3};
+use crate::scene::object::{Hit, Object};
+
+// Axis-aligned box spanning the volume between two opposite corners
+pub struct Cuboid {
+    pub min: Point, // Corner with the lowest x, y and z coordinates
+    pub max: Point, // Corner with the highest x, y and z coordinates
+    pub material: Box<dyn Material>,
+}
+
+impl Cuboid {
+    // Constructs a box from any two opposite corners
+    pub fn new(a: Point, b: Point, material: Box<dyn Material>) -> Self {
+        Self {
+            min: Point::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z())),
+            max: Point::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z())),
+            material,
+        }
+    }
+
+    fn axis_normal(axis: usize, sign: f32) -> Vec3 {
+        match axis {
+            0 => Vec3::new(sign, 0., 0.),
+            1 => Vec3::new(0., sign, 0.),
+            _ => Vec3::new(0., 0., sign),
+        }
+    }
+}
+
+// Where a ray enters and exits an axis-aligned box, and through which axis' faces
+pub struct SlabHits {
+    pub t_enter: f32,
+    pub enter_axis: usize,
+    pub t_exit: f32,
+    pub exit_axis: usize,
+}
+
+// Slab test: the box is the intersection of three slabs (the space between two parallel planes)
+// and the ray is inside the box where it is inside all three slabs at the same time. The ray
+// enters the box at the last slab it enters and exits the box at the first slab it exits.
+pub fn slab_test(min: Point, max: Point, ray: &Ray) -> Option<SlabHits> {
+    let origin = [ray.origin.x(), ray.origin.y(), ray.origin.z()];
+    let direction = [ray.direction.x(), ray.direction.y(), ray.direction.z()];
+    let min = [min.x(), min.y(), min.z()];
+    let max = [max.x(), max.y(), max.z()];
+
+    let mut slabs = SlabHits {
+        t_enter: -f32::INFINITY,
+        enter_axis: 0,
+        t_exit: f32::INFINITY,
+        exit_axis: 0,
+    };
+
+    for axis in 0..3 {
+        if direction[axis] == 0. {
+            // Parallel to the slab, either always inside it or never
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let t0 = (min[axis] - origin[axis]) / direction[axis];
+        let t1 = (max[axis] - origin[axis]) / direction[axis];
+        let (near, far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+
+        if near > slabs.t_enter {
+            slabs.t_enter = near;
+            slabs.enter_axis = axis;
+        }
+        if far < slabs.t_exit {
+            slabs.t_exit = far;
+            slabs.exit_axis = axis;
+        }
+    }
+
+    if slabs.t_enter > slabs.t_exit {
+        return None; // Slabs do not overlap along the ray, box missed
+    }
+
+    Some(slabs)
+}
+
+impl Object for Cuboid {
+    fn material(&self) -> &dyn Material {
+        &*self.material
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        let slabs = match slab_test(self.min, self.max, ray) {
+            Some(slabs) => slabs,
+            None => return (false, None),
+        };
+
+        let direction = [ray.direction.x(), ray.direction.y(), ray.direction.z()];
+
+        // The outward normal at the entry face points against the ray, at the exit face along it
+        let (t, outward_normal) = if within.surrounds(slabs.t_enter) {
+            (
+                slabs.t_enter,
+                Self::axis_normal(slabs.enter_axis, -direction[slabs.enter_axis].signum()),
+            )
+        } else if within.surrounds(slabs.t_exit) {
+            (
+                slabs.t_exit,
+                Self::axis_normal(slabs.exit_axis, direction[slabs.exit_axis].signum()),
+            )
+        } else {
+            return (false, None);
+        };
+
+        ray_debug!("Ray hit box at t {}", t);
+
+        (true, Some(Hit::new(ray, ray.at(t), outward_normal, t)))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Box from {} to {}, material: {}",
+            self.min,
+            self.max,
+            self.material.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::Color;
+
+    fn unit_box() -> Cuboid {
+        Cuboid::new(
+            Point::new(1., 1., 1.),
+            Point::new(-1., -1., -1.),
+            Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    #[test]
+    fn it_orders_its_corners() {
+        let cuboid = unit_box();
+
+        assert_eq!(cuboid.min, Point::new(-1., -1., -1.));
+        assert_eq!(cuboid.max, Point::new(1., 1., 1.));
+    }
+
+    #[test]
+    fn it_is_hit_on_the_face_facing_the_ray() {
+        let cases = [
+            ((0., 0., 5.), (0., 0., -1.), 4., (0., 0., 1.)),
+            ((0., 0., -5.), (0., 0., 1.), 4., (0., 0., -1.)),
+            ((5., 0.5, 0.), (-1., 0., 0.), 4., (1., 0., 0.)),
+            ((0.5, -3., 0.5), (0., 2., 0.), 1., (0., -1., 0.)),
+        ];
+
+        for (origin, direction, expected_t, expected_normal) in cases.iter() {
+            let ray = Ray::new(
+                Point::new(origin.0, origin.1, origin.2),
+                Vec3::new(direction.0, direction.1, direction.2),
+            );
+
+            let (is_hit, hit) = unit_box().hit_by(&ray, Interval::new(0.001, 100.));
+            let hit = hit.unwrap();
+
+            assert!(is_hit);
+            assert!(hit.front_face);
+            assert_eq!(hit.t, *expected_t);
+            assert_eq!(
+                hit.normal,
+                Vec3::new(expected_normal.0, expected_normal.1, expected_normal.2)
+            );
+        }
+    }
+
+    #[test]
+    fn it_is_hit_from_the_inside() {
+        let ray = Ray::new(Point::origin(), Vec3::new(0., 1., 0.));
+
+        let (is_hit, hit) = unit_box().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(!hit.front_face);
+        assert_eq!(hit.t, 1.);
+        assert_eq!(hit.p, Point::new(0., 1., 0.));
+        assert_eq!(hit.normal, Vec3::new(0., -1., 0.)); // Facing against the ray, into the box
+    }
+
+    #[test]
+    fn it_is_missed() {
+        let cases = [
+            ((0., 2., 5.), (0., 0., -1.)),  // Passes over the box
+            ((0., 0., 5.), (0., 0., 1.)),   // Points away from the box
+            ((3., 0., 3.), (-1., 0., 0.2)), // Passes the edge diagonally
+        ];
+
+        for (origin, direction) in cases.iter() {
+            let ray = Ray::new(
+                Point::new(origin.0, origin.1, origin.2),
+                Vec3::new(direction.0, direction.1, direction.2),
+            );
+
+            let (is_hit, hit) = unit_box().hit_by(&ray, Interval::new(0.001, 100.));
+
+            assert!(!is_hit);
+            assert!(hit.is_none());
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::chance::random_f32;
+use crate::math::numbers::Interval;
+use crate::scene::object::{Hit, Object};
+use crate::texture::base::Texture;
+
+// How the opacity from the mask texture decides whether a hit counts
+#[derive(Clone, Copy, Debug)]
+pub enum AlphaMode {
+    Threshold(f32), // Opaque where the opacity is at least this, fully see-through below
+    Stochastic,     // Opaque for that fraction of rays, which averages out to soft edges
+}
+
+// Opacity texture for an object: where it is see-through, hits are ignored and rays carry on as if
+// the object was not there. Much cheaper than a transmissive material for leaves, fences and decals,
+// the ray does not even scatter.
+pub struct AlphaMask {
+    pub texture: Box<dyn Texture>, // Grayscale opacity, 0 is see-through and 1 opaque
+    pub mode: AlphaMode,
+}
+
+impl AlphaMask {
+    pub fn new(texture: Box<dyn Texture>, mode: AlphaMode) -> Self {
+        Self { texture, mode }
+    }
+
+    // Whether the hit counts, or the ray passes through the object there
+    pub fn is_opaque(&self, hit: &Hit) -> bool {
+        let alpha = self.texture.value_scalar(hit.u, hit.v, hit.p);
+
+        match self.mode {
+            AlphaMode::Threshold(threshold) => alpha >= threshold,
+            AlphaMode::Stochastic => random_f32(0., 1.) < alpha,
+        }
+    }
+}
+
+// Cuts holes in an object with an alpha mask, see Scene::trace
+pub struct Cutout {
+    pub object: Box<dyn Object>,
+    pub mask: AlphaMask,
+}
+
+impl Cutout {
+    pub fn new(object: Box<dyn Object>, mask: AlphaMask) -> Self {
+        Self { object, mask }
+    }
+}
+
+impl Object for Cutout {
+    fn material(&self) -> &dyn Material {
+        self.object.material()
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        self.object.hit_by(ray, within)
+    }
+
+    fn hits_along(&self, ray: &Ray) -> Vec<Hit> {
+        self.object.hits_along(ray)
+    }
+
+    fn alpha_mask(&self) -> Option<&AlphaMask> {
+        Some(&self.mask)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Cutout of {} ({:?})",
+            self.object.describe(),
+            self.mask.mode
+        )
+    }
+}
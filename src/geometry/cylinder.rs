@@ -0,0 +1,174 @@
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::numbers::Interval;
+use crate::math::polynomial::solve_quadratic;
+use crate::math::vector::{Point, Vec3};
+use crate::scene::object::{Hit, Object};
+
+// Closed cylinder standing upright (along the y axis) on a circular base
+pub struct Cylinder {
+    pub base_center: Point, // Center of the bottom cap
+    pub radius: f32,
+    pub height: f32, // Distance between the bottom and the top cap
+    pub material: Box<dyn Material>,
+}
+
+impl Cylinder {
+    pub fn new(base_center: Point, radius: f32, height: f32, material: Box<dyn Material>) -> Self {
+        Self {
+            base_center,
+            radius,
+            height,
+            material,
+        }
+    }
+}
+
+impl Object for Cylinder {
+    fn material(&self) -> &dyn Material {
+        &*self.material
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        // Work relative to the base center, the cylinder's axis is the y axis from 0 to height
+        let o = ray.origin - self.base_center;
+        let d = ray.direction;
+
+        // Candidate intersections as (t, outward normal)
+        let mut candidates: Vec<(f32, Vec3)> = Vec::with_capacity(4);
+
+        // Side: the ray's projection on the xz plane intersects the circle x² + z² = r²
+        let a = d.x() * d.x() + d.z() * d.z();
+        if a != 0. {
+            let b = 2. * (o.x() * d.x() + o.z() * d.z());
+            let c = o.x() * o.x() + o.z() * o.z() - self.radius * self.radius;
+
+            for t in solve_quadratic([c as f64, b as f64, a as f64]) {
+                let t = t as f32;
+                let y = o.y() + t * d.y();
+
+                if (0. ..=self.height).contains(&y) {
+                    let p = o + t * d;
+                    let outward_normal = Vec3::new(p.x(), 0., p.z()) / self.radius;
+                    candidates.push((t, outward_normal));
+                }
+            }
+        }
+
+        // Caps: the ray intersects the planes y = 0 and y = height within the radius
+        if d.y() != 0. {
+            for (cap_y, normal_y) in [(0., -1.), (self.height, 1.)] {
+                let t = (cap_y - o.y()) / d.y();
+                let p = o + t * d;
+
+                if p.x() * p.x() + p.z() * p.z() <= self.radius * self.radius {
+                    candidates.push((t, Vec3::new(0., normal_y, 0.)));
+                }
+            }
+        }
+
+        let closest = candidates
+            .into_iter()
+            .filter(|(t, _)| within.surrounds(*t))
+            .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        match closest {
+            Some((t, outward_normal)) => {
+                ray_debug!("Ray hit cylinder at t {}", t);
+
+                (true, Some(Hit::new(ray, ray.at(t), outward_normal, t)))
+            }
+            None => (false, None),
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Cylinder on base {} with radius {} and height {}, material: {}",
+            self.base_center,
+            self.radius,
+            self.height,
+            self.material.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::Color;
+
+    fn cylinder() -> Cylinder {
+        Cylinder::new(
+            Point::new(0., -1., 0.),
+            1.,
+            2.,
+            Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    #[test]
+    fn it_is_hit_on_the_side_and_caps() {
+        let cases = [
+            ((0., 0., 5.), (0., 0., -1.), 4., (0., 0., 1.)), // Side, front
+            ((-5., 0.5, 0.), (1., 0., 0.), 4., (-1., 0., 0.)), // Side, left
+            ((0.5, 5., 0.), (0., -1., 0.), 4., (0., 1., 0.)), // Top cap
+            ((0., -5., 0.5), (0., 1., 0.), 4., (0., -1., 0.)), // Bottom cap
+        ];
+
+        for (origin, direction, expected_t, expected_normal) in cases.iter() {
+            let ray = Ray::new(
+                Point::new(origin.0, origin.1, origin.2),
+                Vec3::new(direction.0, direction.1, direction.2),
+            );
+
+            let (is_hit, hit) = cylinder().hit_by(&ray, Interval::new(0.001, 100.));
+            let hit = hit.unwrap();
+
+            assert!(is_hit);
+            assert!(hit.front_face);
+            assert!((hit.t - expected_t).abs() < 1e-5);
+            assert!(
+                (hit.normal - Vec3::new(expected_normal.0, expected_normal.1, expected_normal.2))
+                    .length()
+                    < 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn it_is_hit_from_the_inside() {
+        let ray = Ray::new(Point::origin(), Vec3::new(1., 0., 0.));
+
+        let (is_hit, hit) = cylinder().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(!hit.front_face);
+        assert!((hit.t - 1.).abs() < 1e-5);
+        assert!((hit.normal - Vec3::new(-1., 0., 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_is_missed() {
+        let cases = [
+            ((0., 2., 5.), (0., 0., -1.)),  // Passes over the top cap
+            ((2., 5., 0.), (0., -1., 0.)),  // Passes next to the cylinder parallel to the axis
+            ((0., 0., 5.), (0., 0., 1.)),   // Points away from the cylinder
+            ((-5., 0., 1.5), (1., 0., 0.)), // Passes beside the cylinder
+        ];
+
+        for (origin, direction) in cases.iter() {
+            let ray = Ray::new(
+                Point::new(origin.0, origin.1, origin.2),
+                Vec3::new(direction.0, direction.1, direction.2),
+            );
+
+            let (is_hit, hit) = cylinder().hit_by(&ray, Interval::new(0.001, 100.));
+
+            assert!(!is_hit);
+            assert!(hit.is_none());
+        }
+    }
+}
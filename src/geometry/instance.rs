@@ -0,0 +1,147 @@
+use super::cutout::AlphaMask;
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::numbers::Interval;
+use crate::math::transform::Transform;
+use crate::scene::object::{Hit, Object};
+use std::rc::Rc;
+
+// Places a shared Object in the scene with its own Transform. The wrapped Object is defined in
+// object space, the Instance moves, rotates and scales it into world space. Many instances can
+// share the same Object, so the geometry itself is not duplicated.
+pub struct Instance {
+    pub object: Rc<dyn Object>,
+    pub transform: Transform,
+}
+
+impl Instance {
+    pub fn new(object: Rc<dyn Object>, transform: Transform) -> Self {
+        Self { object, transform }
+    }
+}
+
+impl Object for Instance {
+    fn material(&self) -> &dyn Material {
+        self.object.material()
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        // Move the ray into object space instead of moving the object into world space. The
+        // direction is deliberately not normalized, that way t means the same in both spaces.
+        let object_ray = Ray::new(
+            self.transform.inverse_point(ray.origin),
+            self.transform.inverse_vector(ray.direction),
+        )
+        .with_wavelength(ray.wavelength)
+        .with_time(ray.time);
+
+        match self.object.hit_by(&object_ray, within) {
+            (true, Some(hit)) => {
+                ray_debug!("Ray hit instance at t {}", hit.t);
+
+                // Transforming the normal keeps its orientation relative to the ray, so the normal
+                // keeps facing against the ray and front_face does not change.
+                (
+                    true,
+                    Some(Hit {
+                        p: self.transform.point(hit.p),
+                        normal: self.transform.normal(hit.normal).unit(),
+                        tangent: self.transform.vector(hit.tangent).unit(),
+                        ..hit
+                    }),
+                )
+            }
+            _ => (false, None),
+        }
+    }
+
+    fn alpha_mask(&self) -> Option<&AlphaMask> {
+        self.object.alpha_mask()
+    }
+
+    fn describe(&self) -> String {
+        format!("Instance of {}", self.object.describe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::sphere::Sphere;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::{Color, Point, Vec3};
+
+    fn unit_sphere() -> Rc<dyn Object> {
+        Rc::new(Sphere::new(
+            Point::origin(),
+            1.,
+            Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5))),
+        ))
+    }
+
+    #[test]
+    fn it_places_an_object_in_the_world() {
+        let instance = Instance::new(
+            unit_sphere(),
+            Transform::identity().translate(Vec3::new(0., 0., -5.)),
+        );
+        let ray = Ray::new(Point::origin(), Vec3::new(0., 0., -1.));
+
+        let (is_hit, hit) = instance.hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(hit.front_face);
+        assert!((hit.t - 4.).abs() < 1e-5);
+        assert!((hit.p - Point::new(0., 0., -4.)).length() < 1e-5);
+        assert!((hit.normal - Vec3::new(0., 0., 1.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_scales_non_uniformly() {
+        // Ellipsoid stretched along x, its side is 3 units away from its center
+        let instance = Instance::new(
+            unit_sphere(),
+            Transform::identity()
+                .scale(Vec3::new(3., 1., 1.))
+                .translate(Vec3::new(0., 0., -5.)),
+        );
+
+        let ray = Ray::new(Point::new(10., 0., -5.), Vec3::new(-1., 0., 0.));
+        let (_, hit) = instance.hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!((hit.t - 7.).abs() < 1e-5);
+        assert!((hit.normal - Vec3::new(1., 0., 0.)).length() < 1e-5);
+
+        // Off-center the normal is tilted the way the stretched surface is, not the way the
+        // sphere's surface was before scaling
+        let ray = Ray::new(Point::new(1.5, 10., -5.), Vec3::new(0., -1., 0.));
+        let (_, hit) = instance.hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        let object_normal = Vec3::new(0.5, (0.75f32).sqrt(), 0.);
+        let expected = Vec3::new(object_normal.x() / 3., object_normal.y(), 0.).unit();
+
+        assert!((hit.normal - expected).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_shares_one_object_between_instances() {
+        let sphere = unit_sphere();
+        let left = Instance::new(
+            sphere.clone(),
+            Transform::identity().translate(Vec3::new(-2., 0., -5.)),
+        );
+        let right = Instance::new(
+            sphere.clone(),
+            Transform::identity().translate(Vec3::new(2., 0., -5.)),
+        );
+
+        let ray = Ray::new(Point::new(-2., 0., 0.), Vec3::new(0., 0., -1.));
+
+        assert!(left.hit_by(&ray, Interval::new(0.001, 100.)).0);
+        assert!(!right.hit_by(&ray, Interval::new(0.001, 100.)).0);
+        assert_eq!(Rc::strong_count(&sphere), 3);
+    }
+}
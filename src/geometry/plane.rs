@@ -0,0 +1,144 @@
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::numbers::Interval;
+use crate::math::onb::Onb;
+use crate::math::vector::{Point, Vec3};
+use crate::scene::object::{Hit, Object};
+
+// Infinite flat plane through a point. Textures repeat across it every tile_size units.
+pub struct Plane {
+    pub point: Point,
+    pub normal: Vec3, // Unit normal, the side it points to is the front of the plane
+    pub tangent: Vec3, // Direction along the plane that u follows, v follows normal x tangent
+    pub tile_size: f32,
+    pub material: Box<dyn Material>,
+}
+
+impl Plane {
+    pub fn new(point: Point, normal: Vec3, material: Box<dyn Material>) -> Self {
+        let normal = normal.unit();
+
+        Self {
+            point,
+            normal,
+            tangent: Onb::from_w(normal).u,
+            tile_size: 1.,
+            material,
+        }
+    }
+
+    pub fn with_tile_size(mut self, tile_size: f32) -> Self {
+        self.tile_size = tile_size;
+        self
+    }
+}
+
+impl Object for Plane {
+    fn material(&self) -> &dyn Material {
+        &*self.material
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        let denominator = self.normal.dot(ray.direction);
+
+        if denominator.abs() < 1e-8 {
+            return (false, None); // Parallel to the plane
+        }
+
+        let t = (self.point - ray.origin).dot(self.normal) / denominator;
+
+        if !within.surrounds(t) {
+            return (false, None);
+        }
+
+        ray_debug!("Ray hit plane at t {}", t);
+
+        let p = ray.at(t);
+        let offset = p - self.point;
+        let bitangent = self.normal.cross(self.tangent);
+
+        let u = (offset.dot(self.tangent) / self.tile_size).rem_euclid(1.);
+        let v = (offset.dot(bitangent) / self.tile_size).rem_euclid(1.);
+
+        (
+            true,
+            Some(
+                Hit::new(ray, p, self.normal, t)
+                    .with_uv(u, v)
+                    .with_tangent(self.tangent),
+            ),
+        )
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Plane through {} with normal {}, material: {}",
+            self.point,
+            self.normal,
+            self.material.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::Color;
+
+    fn ground() -> Plane {
+        Plane::new(
+            Point::new(0., -1., 0.),
+            Vec3::new(0., 1., 0.),
+            Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5))),
+        )
+        .with_tile_size(2.)
+    }
+
+    #[test]
+    fn it_is_hit_from_both_sides() {
+        let ray = Ray::new(Point::new(0.5, 1., 0.), Vec3::new(0., -1., 0.));
+        let (is_hit, hit) = ground().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(hit.front_face);
+        assert_eq!(hit.t, 2.);
+
+        let ray = Ray::new(Point::new(0.5, -3., 0.), Vec3::new(0., 1., 0.));
+        let (_, hit) = ground().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(!hit.front_face);
+        assert_eq!(hit.normal, Vec3::new(0., -1., 0.));
+    }
+
+    #[test]
+    fn it_is_missed_by_parallel_rays() {
+        let ray = Ray::new(Point::origin(), Vec3::new(1., 0., 0.));
+
+        assert!(!ground().hit_by(&ray, Interval::new(0.001, 100.)).0);
+    }
+
+    #[test]
+    fn it_repeats_texture_coordinates() {
+        let plane = ground();
+        let bitangent = plane.normal.cross(plane.tangent);
+
+        let uv_at = |offset: Vec3| {
+            let target = Point::new(0., -1., 0.) + offset;
+            let ray = Ray::new(target + Vec3::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+            let (_, hit) = plane.hit_by(&ray, Interval::new(0.001, 100.));
+            let hit = hit.unwrap();
+
+            (hit.u, hit.v)
+        };
+
+        let (u, v) = uv_at(plane.tangent * 0.5 + bitangent * 1.5);
+        assert!((u - 0.25).abs() < 1e-5 && (v - 0.75).abs() < 1e-5);
+
+        // One tile further along, and one tile back
+        let (u, v) = uv_at(plane.tangent * 2.5 + bitangent * -0.5);
+        assert!((u - 0.25).abs() < 1e-5 && (v - 0.75).abs() < 1e-5);
+    }
+}
@@ -14,10 +14,15 @@ pub const T_MIN: f32 = 0.001;
 pub const T_MAX: f32 = 1000.0; // Arbitrary value for now, what is reasonable?
 
 // Represents a ray originating at a Camera's eye point and propagating through the Scene
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Ray {
     pub origin: Point,
     pub direction: Vec3,
+    // Wavelength in nanometers once the path carries a single wavelength instead of RGB light,
+    // which happens when it passes through a dispersive material
+    pub wavelength: Option<f32>,
+    // Seconds since the camera's shutter opened, moving objects are where they are at this time
+    pub time: f32,
 }
 
 impl Ray {
@@ -26,6 +31,8 @@ impl Ray {
         Self {
             origin: from,
             direction,
+            wavelength: None,
+            time: 0.,
         }
     }
 
@@ -36,9 +43,21 @@ impl Ray {
         Self {
             origin: from,
             direction,
+            wavelength: None,
+            time: 0.,
         }
     }
 
+    pub fn with_wavelength(mut self, wavelength: Option<f32>) -> Self {
+        self.wavelength = wavelength;
+        self
+    }
+
+    pub fn with_time(mut self, time: f32) -> Self {
+        self.time = time;
+        self
+    }
+
     pub fn at(&self, t: f32) -> Point {
         self.origin + t * self.direction
     }
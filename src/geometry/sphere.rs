@@ -1,13 +1,19 @@
 use super::ray::Ray;
 use crate::material::base::Material;
+use crate::math::constants::PI;
 use crate::math::numbers::Interval;
 use crate::scene::object::Hit;
-use crate::{math::vector::Point, scene::object::Object};
+use crate::{
+    math::vector::{Point, Vec3},
+    scene::object::Object,
+};
 
 pub struct Sphere {
     pub center: Point,
     pub radius: f32,
     pub material: Box<dyn Material>,
+    // Units per second the sphere moves while the camera's shutter is open, blurring it
+    pub velocity: Vec3,
 }
 
 impl Sphere {
@@ -16,8 +22,28 @@ impl Sphere {
             center,
             radius,
             material,
+            velocity: Vec3::zero(),
         }
     }
+
+    pub fn with_velocity(mut self, velocity: Vec3) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    // Where the center is `time` seconds after the shutter opened
+    pub fn center_at(&self, time: f32) -> Point {
+        self.center + self.velocity * time
+    }
+
+    // Maps a point on the unit sphere onto texture coordinates like a world map: u goes around the
+    // y axis starting at -x, v goes from the bottom (-y) to the top (+y)
+    fn uv(p: Point) -> (f32, f32) {
+        let theta = (-p.y()).clamp(-1., 1.).acos();
+        let phi = (-p.z()).atan2(p.x()) + PI;
+
+        (phi / (2. * PI), theta / PI)
+    }
 }
 
 impl Object for Sphere {
@@ -25,9 +51,9 @@ impl Object for Sphere {
         &*self.material
     }
 
-    // TODO: write tests
     fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
-        let oc = self.center - ray.origin;
+        let center = self.center_at(ray.time);
+        let oc = center - ray.origin;
         let a = ray.direction.length_squared();
         let h = ray.direction.dot(oc);
         let c = oc.length_squared() - self.radius * self.radius;
@@ -53,9 +79,18 @@ impl Object for Sphere {
 
         // Proper hit, calculate hit point and the outward normal (pointing from the center outward) at that point
         let p = ray.at(t);
-        let outward_normal = (p - self.center) / self.radius; // We make the normal unit length, has a big impact on subsequent calculations!
+        let outward_normal = (p - center) / self.radius; // We make the normal unit length, has a big impact on subsequent calculations!
 
-        (true, Some(Hit::new(&ray, p, outward_normal, t)))
+        let (u, v) = Self::uv(outward_normal);
+        let hit = Hit::new(ray, p, outward_normal, t).with_uv(u, v);
+
+        // Around the y axis like u, except at the poles where u is undefined
+        let tangent = Vec3::new(outward_normal.z(), 0., -outward_normal.x());
+        if tangent.near_zero() {
+            return (true, Some(hit));
+        }
+
+        (true, Some(hit.with_tangent(tangent.unit())))
     }
 
     fn describe(&self) -> String {
@@ -67,3 +102,71 @@ impl Object for Sphere {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+
+    #[test]
+    fn it_maps_texture_coordinates() {
+        let cases = [
+            ((1., 0., 0.), (0.5, 0.5)),
+            ((-1., 0., 0.), (0., 0.5)),
+            ((0., 0., 1.), (0.25, 0.5)),
+            ((0., 0., -1.), (0.75, 0.5)),
+            ((0., 1., 0.), (0.5, 1.)),
+            ((0., -1., 0.), (0.5, 0.)),
+        ];
+
+        for (p, (expected_u, expected_v)) in cases.iter() {
+            let (u, v) = Sphere::uv(Point::new(p.0, p.1, p.2));
+
+            assert!((u - expected_u).abs() < 1e-5, "{p:?}: u {u}");
+            assert!((v - expected_v).abs() < 1e-5, "{p:?}: v {v}");
+        }
+    }
+
+    #[test]
+    fn it_points_its_tangent_along_u() {
+        let sphere = Sphere::new(
+            Point::origin(),
+            1.,
+            Box::new(Lambert::new(Vec3::new(0.5, 0.5, 0.5))),
+        );
+        let ray = Ray::new(Point::new(5., 0.2, 0.3), Vec3::new(-1., 0., 0.));
+
+        let (_, hit) = sphere.hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        // Moving a little along the tangent increases u
+        let step = hit.p + hit.tangent * 1e-2;
+        let (u, _) = Sphere::uv(step.unit());
+
+        assert!(hit.tangent.dot(hit.normal).abs() < 1e-5);
+        assert!((hit.tangent.length() - 1.).abs() < 1e-5);
+        assert!(u > hit.u);
+    }
+
+    #[test]
+    fn it_moves_while_the_shutter_is_open() {
+        let sphere = Sphere::new(
+            Point::new(0., 0., -5.),
+            1.,
+            Box::new(Lambert::new(Vec3::new(0.5, 0.5, 0.5))),
+        )
+        .with_velocity(Vec3::new(10., 0., 0.));
+
+        let ray = Ray::new(Point::new(2., 0., 0.), Vec3::new(0., 0., -1.));
+
+        // Not there yet when the shutter opens, passing by a fifth of a second later
+        assert!(sphere.hit_by(&ray, Interval::new(0., 100.)).1.is_none());
+
+        let hit = sphere
+            .hit_by(&ray.with_time(0.2), Interval::new(0., 100.))
+            .1
+            .unwrap();
+        assert!((hit.t - 4.).abs() < 1e-4);
+        assert!((hit.normal - Vec3::new(0., 0., 1.)).length() < 1e-4);
+    }
+}
@@ -0,0 +1,173 @@
+use super::ray::Ray;
+use crate::material::base::Material;
+use crate::math::numbers::Interval;
+use crate::math::polynomial::{refine_root, solve_quartic};
+use crate::math::vector::{Point, Vec3};
+use crate::scene::object::{Hit, Object};
+
+// Torus (doughnut shape) lying flat in the xz plane, the y axis runs through its hole
+pub struct Torus {
+    pub center: Point,
+    pub major_radius: f32, // Distance from the center to the middle of the tube
+    pub minor_radius: f32, // Radius of the tube
+    pub material: Box<dyn Material>,
+}
+
+impl Torus {
+    pub fn new(
+        center: Point,
+        major_radius: f32,
+        minor_radius: f32,
+        material: Box<dyn Material>,
+    ) -> Self {
+        Self {
+            center,
+            major_radius,
+            minor_radius,
+            material,
+        }
+    }
+}
+
+impl Object for Torus {
+    fn material(&self) -> &dyn Material {
+        &*self.material
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        // Work relative to the center, in double precision because the quartic is ill-conditioned
+        let o = ray.origin - self.center;
+        let (ox, oy, oz) = (o.x() as f64, o.y() as f64, o.z() as f64);
+        let (dx, dy, dz) = (
+            ray.direction.x() as f64,
+            ray.direction.y() as f64,
+            ray.direction.z() as f64,
+        );
+
+        let major2 = (self.major_radius as f64).powi(2);
+        let minor2 = (self.minor_radius as f64).powi(2);
+
+        // Implicit torus: (|p|² + R² - r²)² = 4R²(x² + z²). Substituting p = o + td gives a quartic
+        // in t, with |p|² = at² + 2ft + g.
+        let a = dx * dx + dy * dy + dz * dz;
+        let f = ox * dx + oy * dy + oz * dz;
+        let g = ox * ox + oy * oy + oz * oz;
+        let k = g + major2 - minor2;
+
+        let coefficients = [
+            k * k - 4. * major2 * (g - oy * oy),
+            4. * f * k - 8. * major2 * (f - oy * dy),
+            4. * f * f + 2. * a * k - 4. * major2 * (a - dy * dy),
+            4. * a * f,
+            a * a,
+        ];
+
+        let closest = solve_quartic(coefficients)
+            .into_iter()
+            .map(|t| refine_root(&coefficients, t, 2) as f32)
+            .filter(|t| within.surrounds(*t))
+            .min_by(|a, b| a.total_cmp(b));
+
+        match closest {
+            Some(t) => {
+                ray_debug!("Ray hit torus at t {}", t);
+
+                // The outward normal points away from the closest point on the tube's center circle
+                let p = o + t * ray.direction;
+                let on_circle = Vec3::new(p.x(), 0., p.z()).unit() * self.major_radius;
+                let outward_normal = (p - on_circle).unit();
+
+                (true, Some(Hit::new(ray, ray.at(t), outward_normal, t)))
+            }
+            None => (false, None),
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Torus at {} with major radius {} and minor radius {}, material: {}",
+            self.center,
+            self.major_radius,
+            self.minor_radius,
+            self.material.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::Color;
+
+    fn torus() -> Torus {
+        Torus::new(
+            Point::new(0., 0., -5.),
+            2.,
+            0.5,
+            Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5))),
+        )
+    }
+
+    #[test]
+    fn it_is_hit_on_the_outside_of_the_ring() {
+        // Travels along the x axis through the middle of the torus
+        let ray = Ray::new(Point::new(-10., 0., -5.), Vec3::new(1., 0., 0.));
+
+        let (is_hit, hit) = torus().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(hit.front_face);
+        assert!((hit.t - 7.5).abs() < 1e-4);
+        assert!((hit.normal - Vec3::new(-1., 0., 0.)).length() < 1e-4);
+    }
+
+    #[test]
+    fn it_is_hit_from_above() {
+        // Travels down onto the top of the tube
+        let ray = Ray::new(Point::new(2., 10., -5.), Vec3::new(0., -1., 0.));
+
+        let (is_hit, hit) = torus().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(hit.front_face);
+        assert!((hit.t - 9.5).abs() < 1e-4);
+        assert!((hit.normal - Vec3::new(0., 1., 0.)).length() < 1e-4);
+    }
+
+    #[test]
+    fn it_is_hit_from_inside_the_tube() {
+        let ray = Ray::new(Point::new(2., 0., -5.), Vec3::new(1., 0., 0.));
+
+        let (is_hit, hit) = torus().hit_by(&ray, Interval::new(0.001, 100.));
+        let hit = hit.unwrap();
+
+        assert!(is_hit);
+        assert!(!hit.front_face);
+        assert!((hit.t - 0.5).abs() < 1e-4);
+        assert!((hit.normal - Vec3::new(-1., 0., 0.)).length() < 1e-4);
+    }
+
+    #[test]
+    fn it_is_missed_through_the_hole() {
+        // Travels straight down the y axis through the hole
+        let ray = Ray::new(Point::new(0., 10., -5.), Vec3::new(0., -1., 0.));
+
+        let (is_hit, hit) = torus().hit_by(&ray, Interval::new(0.001, 100.));
+
+        assert!(!is_hit);
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn it_is_missed_when_passing_above() {
+        let ray = Ray::new(Point::new(-10., 1., -5.), Vec3::new(1., 0., 0.));
+
+        let (is_hit, hit) = torus().hit_by(&ray, Interval::new(0.001, 100.));
+
+        assert!(!is_hit);
+        assert!(hit.is_none());
+    }
+}
@@ -4,24 +4,53 @@
 mod debug;
 
 pub mod geometry {
+    pub mod cone;
+    pub mod csg;
+    pub mod cuboid;
+    pub mod cutout;
+    pub mod cylinder;
+    pub mod instance;
+    pub mod plane;
     pub mod ray;
     pub mod sphere;
+    pub mod torus;
 }
 pub mod material {
     pub mod base;
+    pub mod bump_map;
+    pub mod coated;
+    pub mod conductor;
     pub mod dielectric;
     pub mod diffuse;
+    pub mod dispersion;
     pub mod metal;
+    pub mod microfacet;
+    pub mod mix;
+    pub mod normal_map;
+    pub mod phase;
+    pub mod principled;
+    pub mod rough_dielectric;
+    pub mod sheen;
+    pub mod subsurface;
+    #[cfg(test)]
+    pub mod testing;
+    pub mod thin_film;
 }
 pub mod math {
     pub mod angles;
     pub mod chance;
+    pub mod complex;
     pub mod constants;
     pub mod numbers;
+    pub mod onb;
+    pub mod polynomial;
+    pub mod spectrum;
+    pub mod transform;
     pub mod vector;
 }
 pub mod output {
     pub mod image;
+    #[allow(clippy::module_inception)]
     pub mod output;
 }
 pub mod render {
@@ -30,18 +59,53 @@ pub mod render {
     pub mod renderer;
 }
 pub mod scene {
+    pub mod aperture;
     pub mod camera;
+    pub mod exposure;
+    pub mod lens;
     pub mod object;
+    #[allow(clippy::module_inception)]
     pub mod scene;
+    pub mod stereo;
     pub mod viewport;
 }
 pub mod scenes {
+    pub mod bokeh;
+    pub mod brushed_metal_and_velvet;
+    pub mod bumpy_surfaces;
+    pub mod camera_exposure;
+    pub mod cloud;
+    pub mod conductor_orbs;
+    pub mod csg_shapes;
+    pub mod cutouts;
     pub mod diffuse_orb;
     pub mod empty;
+    pub mod foggy_orbs;
+    pub mod frosted_glass;
+    pub mod instances;
     pub mod lambert_orb;
     pub mod metal_orbs_with_ground;
+    pub mod mixed_materials;
     pub mod orb_with_ground_lambert;
     pub mod playground;
+    pub mod primitives;
+    pub mod principled_orbs;
+    pub mod prism;
     pub mod small_orb_in_front_of_larger_one;
+    pub mod soap_bubble;
+    pub mod subsurface_orbs;
+    pub mod tinted_glass;
     pub mod touching_spheres;
 }
+pub mod texture {
+    pub mod base;
+    pub mod checker;
+    pub mod image;
+    pub mod solid;
+}
+pub mod volume {
+    pub mod constant_medium;
+    pub mod density_grid;
+    pub mod fog;
+    pub mod grid_medium;
+}
@@ -5,6 +5,7 @@
 #[macro_use]
 mod debug;
 
+use std::path::Path;
 use std::time::Instant;
 // Clap is used to define the cli declaratively
 use clap::{Parser, Subcommand, ValueEnum};
@@ -31,10 +32,15 @@ struct Cli {
 enum Commands {
     Render {
         scene: SceneName,
+        // {scene} and {camera} in the path are replaced by their names, rendering several cameras
+        // without {camera} adds the camera name before the extension
         #[arg(short, long, default_value = "out/out.png")]
         output_image: String,
-        #[arg(short, long, default_value = "main")]
-        camera: String,
+        // One or more cameras, separated by commas
+        #[arg(short, long, default_value = "main", value_delimiter = ',', num_args = 1..)]
+        camera: Vec<String>,
+        #[arg(long, conflicts_with = "camera")]
+        all_cameras: bool,
         #[arg(short, long, default_value_t = Algorithm::PathTracer, value_enum)]
         algorithm: Algorithm,
         #[arg(long, default_value_t = DEFAULT_WIDTH)]
@@ -42,6 +48,8 @@ enum Commands {
     },
     Debug {
         scene: SceneName,
+        #[arg(short, long, default_value = "main")]
+        camera: String,
         #[arg(short, long, default_value_t = Algorithm::PathTracer, value_enum)]
         algorithm: Algorithm,
         #[arg(long, default_value_t = DEFAULT_WIDTH)]
@@ -59,18 +67,37 @@ pub enum Algorithm {
     PathTracerLowRes,
     PathTracer,
     PathTracerHighRes,
+    PathTracerSpectral,
 }
 
 // Available scenes
 #[derive(ValueEnum, Clone, Copy, Debug)]
 enum SceneName {
+    Bokeh,
+    BrushedMetalAndVelvet,
+    BumpySurfaces,
+    CameraExposure,
+    Cloud,
+    ConductorOrbs,
+    CsgShapes,
+    Cutouts,
     DiffuseOrb,
     Empty,
+    FoggyOrbs,
+    FrostedGlass,
+    Instances,
     LambertOrb,
     MetalOrbsWithGround,
+    MixedMaterials,
     OrbWithGroundLambert,
     Playground,
+    Primitives,
+    PrincipledOrbs,
+    Prism,
     SmallOrbInFrontOfLargerOne,
+    SoapBubble,
+    SubsurfaceOrbs,
+    TintedGlass,
     TouchingSpheres,
 }
 
@@ -84,21 +111,45 @@ fn main() {
             algorithm,
             scene,
             camera,
+            all_cameras,
             output_image,
             width,
         }) => {
+            let scene_name = scene;
             let scene = select_scene(*scene);
 
-            let mut renderer = select_renderer(&algorithm);
+            let cameras = if *all_cameras {
+                scene.cameras.iter().map(|c| c.name.clone()).collect()
+            } else {
+                camera.clone()
+            };
+
+            // Check all cameras up front rather than failing after rendering some of them
+            for camera in &cameras {
+                if scene.get_camera(camera).is_none() {
+                    let names: Vec<&str> = scene.cameras.iter().map(|c| c.name.as_str()).collect();
+                    panic!(
+                        "Camera {camera} not found, the scene has {}",
+                        names.join(", ")
+                    );
+                }
+            }
+
+            let mut renderer = select_renderer(algorithm);
+
+            for camera in &cameras {
+                let path = output_path(output_image, *scene_name, camera, cameras.len() > 1);
 
-            match renderer.render(&scene, String::from(camera), *width, OutputType::PNG) {
-                Ok(output) => output.save(output_image),
-                Err(err) => panic!("Error rendering {err}"),
+                match renderer.render(&scene, camera.clone(), *width, OutputType::PNG) {
+                    Ok(output) => output.save(&path),
+                    Err(err) => panic!("Error rendering {err}"),
+                }
             }
         }
         Some(Commands::Debug {
             algorithm,
             scene,
+            camera,
             width,
             x,
             y,
@@ -107,9 +158,9 @@ fn main() {
 
             let scene = select_scene(*scene);
 
-            let mut renderer = select_renderer(&algorithm);
+            let mut renderer = select_renderer(algorithm);
 
-            renderer.debug_ray(*x, *y, &scene, String::from("main"), *width)
+            renderer.debug_ray(*x, *y, &scene, camera.clone(), *width)
         }
         _ => {
             println!("Specify a subcommand");
@@ -119,6 +170,40 @@ fn main() {
     println!("Command completed in {:.2?}", start.elapsed());
 }
 
+// Output path for a camera: fills in the scene and camera names. When rendering several cameras to
+// a path without {camera}, the camera name is added before the extension so the images don't
+// overwrite each other. Only the file name is split, dots in directory names are left alone.
+fn output_path(template: &str, scene: SceneName, camera: &str, several: bool) -> String {
+    let path = Path::new(template);
+
+    let template = match (path.file_stem(), path.extension()) {
+        (Some(stem), extension) if several && !template.contains("{camera}") => {
+            let file_name = match extension {
+                Some(extension) => format!(
+                    "{}_{{camera}}.{}",
+                    stem.to_string_lossy(),
+                    extension.to_string_lossy()
+                ),
+                None => format!("{}_{{camera}}", stem.to_string_lossy()),
+            };
+
+            path.with_file_name(file_name)
+                .to_string_lossy()
+                .into_owned()
+        }
+        _ => String::from(template),
+    };
+
+    let scene = scene
+        .to_possible_value()
+        .map(|value| String::from(value.get_name()))
+        .unwrap_or_default();
+
+    template
+        .replace("{scene}", &scene)
+        .replace("{camera}", camera)
+}
+
 // Returns a renderer object for the specified Algorithm
 fn select_renderer(algorithm: &Algorithm) -> Box<dyn Renderer> {
     match &algorithm {
@@ -126,20 +211,89 @@ fn select_renderer(algorithm: &Algorithm) -> Box<dyn Renderer> {
         Algorithm::PathTracer => Box::new(PathTracer::new(100, 50)),
         Algorithm::PathTracerLowRes => Box::new(PathTracer::new(10, 50)),
         Algorithm::PathTracerHighRes => Box::new(PathTracer::new(1000, 100)),
+        Algorithm::PathTracerSpectral => Box::new(PathTracer::new_spectral(100, 50)),
     }
 }
 
 fn select_scene(name: SceneName) -> Scene {
     match name {
+        SceneName::Bokeh => scenes::bokeh::generate(),
+        SceneName::BrushedMetalAndVelvet => scenes::brushed_metal_and_velvet::generate(),
+        SceneName::BumpySurfaces => scenes::bumpy_surfaces::generate(),
+        SceneName::CameraExposure => scenes::camera_exposure::generate(),
+        SceneName::Cloud => scenes::cloud::generate(),
+        SceneName::ConductorOrbs => scenes::conductor_orbs::generate(),
+        SceneName::CsgShapes => scenes::csg_shapes::generate(),
+        SceneName::Cutouts => scenes::cutouts::generate(),
         SceneName::Empty => scenes::empty::generate(),
+        SceneName::FoggyOrbs => scenes::foggy_orbs::generate(),
+        SceneName::FrostedGlass => scenes::frosted_glass::generate(),
         SceneName::DiffuseOrb => scenes::diffuse_orb::generate(),
+        SceneName::Instances => scenes::instances::generate(),
         SceneName::LambertOrb => scenes::lambert_orb::generate(),
         SceneName::MetalOrbsWithGround => scenes::metal_orbs_with_ground::generate(),
+        SceneName::MixedMaterials => scenes::mixed_materials::generate(),
         SceneName::OrbWithGroundLambert => scenes::orb_with_ground_lambert::generate(),
         SceneName::Playground => scenes::playground::generate(),
+        SceneName::Primitives => scenes::primitives::generate(),
+        SceneName::PrincipledOrbs => scenes::principled_orbs::generate(),
+        SceneName::Prism => scenes::prism::generate(),
         SceneName::SmallOrbInFrontOfLargerOne => {
             scenes::small_orb_in_front_of_larger_one::generate()
         }
+        SceneName::SoapBubble => scenes::soap_bubble::generate(),
+        SceneName::SubsurfaceOrbs => scenes::subsurface_orbs::generate(),
+        SceneName::TintedGlass => scenes::tinted_glass::generate(),
         SceneName::TouchingSpheres => scenes::touching_spheres::generate(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_fills_in_the_scene_and_camera_names() {
+        assert_eq!(
+            output_path(
+                "renders/{scene}-{camera}.png",
+                SceneName::Prism,
+                "main",
+                false
+            ),
+            "renders/prism-main.png"
+        );
+        assert_eq!(
+            output_path("{camera}.png", SceneName::Prism, "wide", true),
+            "wide.png"
+        );
+    }
+
+    #[test]
+    fn it_adds_the_camera_name_when_rendering_several_cameras() {
+        assert_eq!(
+            output_path("output.png", SceneName::Prism, "wide", false),
+            "output.png"
+        );
+        assert_eq!(
+            output_path("output.png", SceneName::Prism, "wide", true),
+            "output_wide.png"
+        );
+        assert_eq!(
+            output_path("output", SceneName::Prism, "wide", true),
+            "output_wide"
+        );
+    }
+
+    #[test]
+    fn it_only_splits_the_file_name() {
+        assert_eq!(
+            output_path("renders.v2/output", SceneName::Prism, "wide", true),
+            "renders.v2/output_wide"
+        );
+        assert_eq!(
+            output_path("renders.v2/{scene}.png", SceneName::Prism, "wide", true),
+            "renders.v2/prism_wide.png"
+        );
+    }
+}
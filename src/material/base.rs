@@ -5,4 +5,11 @@ use crate::scene::object::Hit;
 pub trait Material {
     fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)>;
     fn describe(&self) -> String;
+
+    // Whether the material treats every wavelength differently, like Dispersion bending them apart
+    // or a ThinFilm reflecting some more than others. The spectral renderer then only follows the
+    // hero wavelength the ray carries.
+    fn is_dispersive(&self) -> bool {
+        false
+    }
 }
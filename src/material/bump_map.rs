@@ -0,0 +1,110 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::normal_map::{perturb_normal, tangent_frame};
+use crate::math::vector::{Color, Vec3};
+use crate::scene::object::Hit;
+use crate::texture::base::Texture;
+
+// Distance in texture coordinates over which the height's slope is measured, about a pixel of a
+// 1000 pixel texture
+const DELTA: f32 = 1e-3;
+
+// Surface detail from a height map (any grayscale texture) instead of a normal map: the surface
+// pretends to be raised by the height, and the normal tilts along the slope of the height. The
+// slope is calculated with finite differences, sampling the height a tiny step along the tangent
+// and bitangent.
+pub struct BumpMap {
+    pub material: Box<dyn Material>,
+    pub height: Box<dyn Texture>,
+    pub strength: f32, // Scales the height, 0 leaves the surface flat
+}
+
+impl BumpMap {
+    pub fn new(material: Box<dyn Material>, height: Box<dyn Texture>) -> Self {
+        Self {
+            material,
+            height,
+            strength: 1.,
+        }
+    }
+
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+impl Material for BumpMap {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        let (tangent, bitangent, _) = tangent_frame(hit);
+
+        // Solid textures use the point instead of (u, v), so step along the surface as well
+        let height = self.height.value_scalar(hit.u, hit.v, hit.p);
+        let height_u = self
+            .height
+            .value_scalar(hit.u + DELTA, hit.v, hit.p + tangent * DELTA);
+        let height_v = self
+            .height
+            .value_scalar(hit.u, hit.v + DELTA, hit.p + bitangent * DELTA);
+
+        let slope_u = self.strength * (height_u - height) / DELTA;
+        let slope_v = self.strength * (height_v - height) / DELTA;
+
+        // The normal leans away from the direction the surface rises in
+        let local_normal = Vec3::new(-slope_u, -slope_v, 1.);
+
+        self.material
+            .scatter(ray, &perturb_normal(ray, hit, local_normal))
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.material.is_dispersive()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Bump map {} with strength {} over ({})",
+            self.height.describe(),
+            self.strength,
+            self.material.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::testing::ShowNormal;
+    use crate::math::vector::Point;
+
+    // Height rising along x, a ramp with a slope of 1
+    struct Ramp;
+
+    impl Texture for Ramp {
+        fn value(&self, _u: f32, _v: f32, p: Point) -> Color {
+            Color::new(p.x(), p.x(), p.x())
+        }
+
+        fn describe(&self) -> String {
+            String::from("ramp")
+        }
+    }
+
+    #[test]
+    fn it_tilts_the_normal_down_the_slope() {
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.)
+            .with_tangent(Vec3::new(1., 0., 0.));
+
+        let bumped = BumpMap::new(Box::new(ShowNormal), Box::new(Ramp));
+        let (_, normal) = bumped.scatter(&ray, &hit).unwrap();
+
+        // A 45° ramp rising along x has a normal leaning back towards -x
+        assert!((normal - Vec3::new(-1., 1., 0.).unit()).length() < 1e-3);
+
+        let flat = BumpMap::new(Box::new(ShowNormal), Box::new(Ramp)).with_strength(0.);
+        let (_, normal) = flat.scatter(&ray, &hit).unwrap();
+
+        assert!((normal - Vec3::new(0., 1., 0.)).length() < 1e-5);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::diffuse::Lambert;
+use crate::material::microfacet::fresnel_dielectric;
+use crate::math::chance::random_f32;
+use crate::math::vector::Color;
+use crate::scene::object::Hit;
+
+// Any material under a thin, smooth layer of dielectric, like varnish on wood or lacquer on
+// paint. The layer mirrors part of the light depending on the angle (little when looking straight
+// at it, most at grazing angles), the rest passes through to the base material and is tinted on
+// its way in and out.
+//
+// The layer is treated as infinitely thin: light is not refracted on its way through, and light
+// bouncing around between the layer and the base is not simulated.
+pub struct Coated {
+    pub base: Box<dyn Material>,
+    pub refractive_index: f32,
+    pub tint: Color, // Color of the coat itself, white for a clear coat
+}
+
+impl Coated {
+    pub fn new(base: Box<dyn Material>, refractive_index: f32) -> Self {
+        Self {
+            base,
+            refractive_index,
+            tint: Color::new(1., 1., 1.),
+        }
+    }
+
+    // Glossy diffuse surface: a clear varnish over Lambert
+    pub fn new_diffuse(albedo: Color) -> Self {
+        Self::new(Box::new(Lambert::new(albedo)), 1.5)
+    }
+
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+}
+
+impl Material for Coated {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        let direction = ray.direction.unit();
+        let cos_theta = (-direction).dot(hit.normal);
+
+        if random_f32(0., 1.) < fresnel_dielectric(cos_theta, self.refractive_index) {
+            ray_debug!("COATED - reflecting off the coat");
+
+            return Some((
+                Ray::new(hit.p, direction.reflect(&hit.normal)),
+                Color::new(1., 1., 1.),
+            ));
+        }
+
+        ray_debug!("COATED - passing through the coat");
+
+        // Through the coat twice, on the way in and on the way out
+        self.base
+            .scatter(ray, hit)
+            .map(|(scattered, attenuation)| (scattered, attenuation * self.tint * self.tint))
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.base.is_dispersive()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Coated material with refractive index {} and tint {} over ({})",
+            self.refractive_index,
+            self.tint,
+            self.base.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::{Point, Vec3};
+
+    // Fraction of samples mirrored by the coat, for a ray arriving from `direction`
+    fn mirrored(direction: Vec3) -> f32 {
+        let coated = Coated::new_diffuse(Color::new(0.5, 0.5, 0.5));
+        let ray = Ray::new(Point::origin() - direction, direction);
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+        let samples = 20000;
+        let mirrored = (0..samples)
+            .filter(|_| coated.scatter(&ray, &hit).unwrap().1 == Color::new(1., 1., 1.))
+            .count();
+
+        mirrored as f32 / samples as f32
+    }
+
+    #[test]
+    fn it_reflects_more_at_grazing_angles() {
+        // 4% when looking straight at it
+        assert!((mirrored(Vec3::new(0., -1., 0.)) - 0.04).abs() < 0.01);
+
+        assert!(mirrored(Vec3::new(10., -1., 0.)) > 0.4);
+    }
+
+    #[test]
+    fn it_tints_light_passing_through() {
+        let coated = Coated::new_diffuse(Color::new(1., 1., 1.)).with_tint(Color::new(1., 0.5, 0.));
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+        let through = (0..100)
+            .filter_map(|_| coated.scatter(&ray, &hit))
+            .find(|(_, attenuation)| *attenuation != Color::new(1., 1., 1.))
+            .unwrap();
+
+        assert_eq!(through.1, Color::new(1., 0.25, 0.));
+    }
+}
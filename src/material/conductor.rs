@@ -0,0 +1,288 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::microfacet::{Ggx, fresnel_conductor, reflect};
+use crate::material::thin_film::ThinFilm;
+use crate::math::complex::Complex;
+use crate::math::onb::Onb;
+use crate::math::vector::Color;
+use crate::scene::object::Hit;
+
+// Physically based rough metal: a GGX microfacet surface where every facet is a perfect mirror
+// reflecting according to the metal's complex refractive index.
+//
+// Unlike Metal, roughness spreads the reflections the way real brushed or sandblasted metal does,
+// and the color comes from measured optical constants instead of an albedo. Light that bounces off
+// more than one facet before leaving the surface is not simulated, so very rough conductors lose a
+// bit of energy and come out slightly darker than they should.
+//
+// Brushed or machined metal is rougher in one direction than the other: brushing leaves fine
+// grooves, and facets tilt much more across the grooves than along them. This stretches highlights
+// into streaks perpendicular to the grooves. The grooves follow the tangent of the hit, e.g. around
+// a sphere from west to east.
+pub struct Conductor {
+    pub eta: Color,     // Real part of the refractive index, per color channel
+    pub k: Color, // Extinction coefficient (imaginary part of the refractive index), per channel
+    pub roughness: f32, // Along the tangent (the grooves) when anisotropic
+    pub roughness_across: Option<f32>, // Across the tangent, when rougher in one direction
+    // Oxide layer or coating, like on anodized titanium or heat tinted steel
+    pub thin_film: Option<ThinFilm>,
+    distribution: Ggx,
+}
+
+impl Conductor {
+    pub fn new(eta: Color, k: Color, roughness: f32) -> Self {
+        Self {
+            eta,
+            k,
+            roughness,
+            roughness_across: None,
+            thin_film: None,
+            distribution: Ggx::from_roughness(roughness),
+        }
+    }
+
+    // Brushed along the tangent: `roughness` along the grooves, `roughness_across` across them
+    pub fn with_anisotropy(mut self, roughness_across: f32) -> Self {
+        self.roughness_across = Some(roughness_across);
+        self.distribution = Ggx::from_anisotropic_roughness(self.roughness, roughness_across);
+        self
+    }
+
+    pub fn with_thin_film(mut self, thin_film: ThinFilm) -> Self {
+        self.thin_film = Some(thin_film);
+        self
+    }
+
+    // Complex refractive index at any wavelength, interpolated between the channels
+    fn refractive_index(&self, wavelength: f32) -> Complex {
+        let (first, second, t) = if wavelength < 550. {
+            (2, 1, ((wavelength - 450.) / 100.).max(0.))
+        } else {
+            (1, 0, ((wavelength - 550.) / 100.).min(1.))
+        };
+
+        let channel = |color: Color, i: usize| [color.x(), color.y(), color.z()][i];
+        let lerp = |color: Color| channel(color, first) * (1. - t) + channel(color, second) * t;
+
+        Complex::new(lerp(self.eta), lerp(self.k))
+    }
+
+    // Optical constants below are sampled at roughly 650nm, 550nm and 450nm for the red, green and
+    // blue channel respectively.
+
+    pub fn new_gold(roughness: f32) -> Self {
+        Self::new(
+            Color::new(0.143, 0.374, 1.442),
+            Color::new(3.983, 2.385, 1.603),
+            roughness,
+        )
+    }
+
+    pub fn new_copper(roughness: f32) -> Self {
+        Self::new(
+            Color::new(0.200, 0.924, 1.102),
+            Color::new(3.912, 2.452, 2.142),
+            roughness,
+        )
+    }
+
+    pub fn new_aluminium(roughness: f32) -> Self {
+        Self::new(
+            Color::new(1.657, 0.880, 0.521),
+            Color::new(9.224, 6.270, 4.837),
+            roughness,
+        )
+    }
+
+    // Dull gray on its own, but anodizing grows a thin oxide layer with vivid colors on it
+    pub fn new_titanium(roughness: f32) -> Self {
+        Self::new(
+            Color::new(2.741, 2.542, 2.160),
+            Color::new(3.814, 3.435, 2.930),
+            roughness,
+        )
+    }
+
+    pub fn new_silver(roughness: f32) -> Self {
+        Self::new(
+            Color::new(0.155, 0.117, 0.138),
+            Color::new(4.828, 3.122, 2.147),
+            roughness,
+        )
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        // Work in a local frame with the normal along z, wo points back towards where the ray came
+        // from. The tangent is along x, so anisotropic roughness is applied in the right directions.
+        let frame = Onb::from_w_towards(hit.normal, hit.tangent);
+        let wo = frame.to_local(-ray.direction.unit());
+
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        // Pick a facet the ray can see and mirror the ray on it
+        let m = self.distribution.sample_visible_normal(wo);
+        let wi = reflect(wo, m);
+
+        if wi.z() <= 0. {
+            ray_debug!("CONDUCTOR - reflected into the surface, absorbed");
+
+            return None; // Reflected into another facet, multiple scattering is not simulated
+        }
+
+        // With visible normal sampling most terms of the microfacet BRDF cancel out against the
+        // sampling probability, leaving the Fresnel term and the shadowing of the outgoing ray
+        let fresnel = match (&self.thin_film, ray.wavelength) {
+            // A ray carrying a single wavelength only needs the reflectance at that wavelength
+            (Some(film), Some(wavelength)) => {
+                let inside = self.refractive_index(wavelength);
+                let reflectance = film.reflectance(hit, wo.dot(m), 1., inside, wavelength);
+
+                Color::new(reflectance, reflectance, reflectance)
+            }
+            (Some(film), None) => film.reflectance_rgb(hit, wo.dot(m), 1., |wavelength| {
+                self.refractive_index(wavelength)
+            }),
+            (None, _) => fresnel_conductor(wo.dot(m), self.eta, self.k),
+        };
+        let shadowing = self.distribution.g2(wo, wi) / self.distribution.g1(wo);
+
+        Some((Ray::new(hit.p, frame.to_world(wi)), fresnel * shadowing))
+    }
+
+    // The film reflects every wavelength differently
+    fn is_dispersive(&self) -> bool {
+        self.thin_film.is_some()
+    }
+
+    fn describe(&self) -> String {
+        let film = match &self.thin_film {
+            Some(film) => format!(", coated with a {}", film.describe()),
+            None => String::new(),
+        };
+
+        let roughness = match self.roughness_across {
+            Some(across) => format!("{} along the tangent, {} across", self.roughness, across),
+            None => format!("{}", self.roughness),
+        };
+
+        format!(
+            "Conductor material with eta {}, k {} and roughness {}{}",
+            self.eta, self.k, roughness, film
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::{Point, Vec3};
+
+    fn hit_from_above(direction: Vec3) -> (Ray, Hit) {
+        let ray = Ray::new(Point::new(0., 1., 0.) - direction, direction);
+        let hit = Hit::new(&ray, Point::new(0., 1., 0.), Vec3::new(0., 1., 0.), 1.);
+
+        (ray, hit)
+    }
+
+    #[test]
+    fn it_mirrors_when_smooth() {
+        let gold = Conductor::new_gold(0.);
+        let (ray, hit) = hit_from_above(Vec3::new(1., -1., 0.));
+
+        let (scattered, attenuation) = gold.scatter(&ray, &hit).unwrap();
+
+        assert!((scattered.direction - Vec3::new(1., 1., 0.).unit()).length() < 1e-3);
+        assert!(attenuation.x() > attenuation.z()); // Gold reflects more red than blue
+    }
+
+    #[test]
+    fn it_reflects_its_fresnel_color_at_normal_incidence_when_smooth() {
+        let copper = Conductor::new_copper(0.);
+        let (ray, hit) = hit_from_above(Vec3::new(0., -1., 0.));
+
+        let (_, attenuation) = copper.scatter(&ray, &hit).unwrap();
+        let expected = fresnel_conductor(1., copper.eta, copper.k);
+
+        assert!((attenuation - expected).length() < 1e-3);
+    }
+
+    #[test]
+    fn it_never_creates_energy() {
+        let aluminium = Conductor::new_aluminium(0.7);
+        let (ray, hit) = hit_from_above(Vec3::new(0.5, -1., 0.2));
+
+        for _ in 0..1000 {
+            if let Some((scattered, attenuation)) = aluminium.scatter(&ray, &hit) {
+                assert!(scattered.direction.dot(hit.normal) > 0.);
+                assert!(attenuation.x() <= 1. && attenuation.y() <= 1. && attenuation.z() <= 1.);
+            }
+        }
+    }
+
+    #[test]
+    fn it_spreads_reflections_across_the_grooves() {
+        // Grooves along x: smooth along the tangent, rough across it
+        let brushed = Conductor::new_aluminium(0.05).with_anisotropy(0.6);
+
+        let (ray, hit) = hit_from_above(Vec3::new(0., -1., 0.));
+        let hit = hit.with_tangent(Vec3::new(1., 0., 0.));
+
+        let (mut along, mut across) = (0., 0.);
+        for _ in 0..1000 {
+            if let Some((scattered, _)) = brushed.scatter(&ray, &hit) {
+                let direction = scattered.direction.unit();
+
+                along += direction.x().abs();
+                across += direction.z().abs();
+            }
+        }
+
+        assert!(across > 5. * along, "{along} vs {across}");
+    }
+
+    #[test]
+    fn it_tints_reflections_with_a_thin_film() {
+        // Titanium reflects all colors about equally, an oxide layer on top colors it
+        let titanium = Conductor::new_titanium(0.);
+        let anodized = Conductor::new_titanium(0.).with_thin_film(ThinFilm::new(100., 2.4));
+        let (ray, hit) = hit_from_above(Vec3::new(0., -1., 0.));
+
+        let (_, bare) = titanium.scatter(&ray, &hit).unwrap();
+        let (_, tinted) = anodized.scatter(&ray, &hit).unwrap();
+
+        let spread = |c: Color| c.x().max(c.y()).max(c.z()) - c.x().min(c.y()).min(c.z());
+        assert!(spread(tinted) > 2. * spread(bare), "{bare} vs {tinted}");
+        assert!(tinted.x() <= 1. && tinted.y() <= 1. && tinted.z() <= 1.);
+    }
+
+    #[test]
+    fn it_reflects_a_single_wavelength_by_the_film_at_that_wavelength() {
+        let film = ThinFilm::new(100., 2.4);
+        let anodized = Conductor::new_titanium(0.).with_thin_film(ThinFilm::new(100., 2.4));
+
+        // The spectral renderer only follows the hero wavelength through the film
+        assert!(anodized.is_dispersive());
+
+        let (ray, hit) = hit_from_above(Vec3::new(0., -1., 0.));
+
+        let reflect = |wavelength: f32| {
+            let ray = ray.with_wavelength(Some(wavelength));
+            let (_, attenuation) = anodized.scatter(&ray, &hit).unwrap();
+            let inside = anodized.refractive_index(wavelength);
+
+            // A single wavelength has no color, only an intensity
+            assert!(attenuation.x() == attenuation.y() && attenuation.y() == attenuation.z());
+            assert!(
+                (attenuation.x() - film.reflectance(&hit, 1., 1., inside, wavelength)).abs() < 1e-3
+            );
+
+            attenuation.x()
+        };
+
+        assert!((reflect(450.) - reflect(650.)).abs() > 0.05);
+    }
+}
@@ -1,7 +1,11 @@
 use crate::geometry::ray::Ray;
 use crate::material::base::Material;
+use crate::material::dispersion::Dispersion;
+use crate::material::thin_film::ThinFilm;
 use crate::math::chance::random_f32;
-use crate::math::vector::Color;
+use crate::math::complex::Complex;
+use crate::math::spectrum::{sample_wavelength, wavelength_to_rgb};
+use crate::math::vector::{Color, Vec3};
 use crate::scene::object::Hit;
 
 // Dielectric materials (like glass and water) both reflect and refract incoming rays.
@@ -18,6 +22,14 @@ pub struct Dielectric {
     // (Nobody succeeded yet in calculating refractive indices for a material from first principles)
     pub refractive_index: f32,
     pub attenuation: Color,
+    // Absorption coefficient per unit of distance travelled inside the material (Beer-Lambert law).
+    // Unlike attenuation, which is applied at every interface, this makes thick glass darker than
+    // thin glass. Zero absorbs nothing.
+    pub absorption: Color,
+    // Wavelength dependent refractive index, refractive_index is used when not set
+    pub dispersion: Option<Dispersion>,
+    // Coating changing the color of reflections, like the film of a soap bubble
+    pub thin_film: Option<ThinFilm>,
 }
 
 impl Dielectric {
@@ -25,9 +37,57 @@ impl Dielectric {
         Self {
             refractive_index,
             attenuation,
+            absorption: Color::zero(),
+            dispersion: None,
+            thin_film: None,
         }
     }
 
+    // Optical glass with noticeable dispersion, for prisms and lenses
+    pub fn new_bk7() -> Self {
+        Self::new(1.5168, Color::new(1., 1., 1.)).with_dispersion(Dispersion::bk7())
+    }
+
+    // Diamond bends light a lot and disperses it strongly, hence its fire
+    pub fn new_diamond() -> Self {
+        Self::new(2.417, Color::new(1., 1., 1.)).with_dispersion(Dispersion::diamond())
+    }
+
+    pub fn with_dispersion(mut self, dispersion: Dispersion) -> Self {
+        self.dispersion = Some(dispersion);
+        self
+    }
+
+    pub fn with_thin_film(mut self, thin_film: ThinFilm) -> Self {
+        self.thin_film = Some(thin_film);
+        self
+    }
+
+    // A soap bubble: a soap film with air on both sides, so light passes straight through it
+    pub fn new_bubble(thin_film: ThinFilm) -> Self {
+        Self::new(1., Color::new(1., 1., 1.)).with_thin_film(thin_film)
+    }
+
+    // Light of the given color keeps that color after travelling `distance` through the material,
+    // more saturated and darker before, lighter after. Handy to pick absorption by eye.
+    pub fn with_absorption(mut self, color: Color, distance: f32) -> Self {
+        self.absorption = Color::new(
+            -color.x().max(1e-6).ln() / distance,
+            -color.y().max(1e-6).ln() / distance,
+            -color.z().max(1e-6).ln() / distance,
+        );
+        self
+    }
+
+    // Fraction of light left after travelling `distance` through the material
+    pub fn transmittance(&self, distance: f32) -> Color {
+        Color::new(
+            (-self.absorption.x() * distance).exp(),
+            (-self.absorption.y() * distance).exp(),
+            (-self.absorption.z() * distance).exp(),
+        )
+    }
+
     pub fn new_glass() -> Self {
         Self::new(
             1.5,
@@ -37,55 +97,307 @@ impl Dielectric {
 
     // Schlick's estimation of whether a ray will reflect or refract when interacting with glass
     pub fn reflectance(&self, cos_theta: f32, refractive_index: f32) -> f32 {
-        let r0 = (1. - refractive_index) / (1.0 + refractive_index);
-        let r0 = r0 * r0;
+        schlick(cos_theta, refractive_index)
+    }
+}
+
+fn schlick(cos_theta: f32, refractive_index: f32) -> f32 {
+    let r0 = (1. - refractive_index) / (1.0 + refractive_index);
+    let r0 = r0 * r0;
+
+    r0 + (1.0 - r0) * (1.0 - cos_theta).powi(5)
+}
+
+// Direction a ray continues in after hitting the smooth interface between the outside and a
+// material with the given refractive index: either reflected or refracted, picked at random by
+// how much light the interface reflects. Shared with other materials bounded by such an interface.
+pub(crate) fn interface_direction(direction: Vec3, hit: &Hit, refractive_index: f32) -> Vec3 {
+    let normal = hit.normal;
+
+    let ri = if hit.front_face {
+        ray_debug!("DIELECTRIC - hit front face");
+
+        1.0 / refractive_index
+    } else {
+        ray_debug!("DIELECTRIC - hit back face");
+
+        refractive_index
+    };
 
-        r0 + (1.0 - r0) * (1.0 - cos_theta).powi(5)
+    let unit_direction = direction.unit();
+
+    let cos_theta = (-unit_direction).dot(normal).min(1.);
+    let sin_theta = (1. - cos_theta * cos_theta).sqrt();
+
+    let cannot_refract = ri * sin_theta > 1.0;
+
+    if cannot_refract || (schlick(cos_theta, ri) > random_f32(0., 1.)) {
+        ray_debug!("DIELECTRIC - reflecting");
+
+        unit_direction.reflect(&normal)
+    } else {
+        ray_debug!("DIELECTRIC - refracting");
+
+        unit_direction.refract(&normal, ri)
     }
 }
 
-impl Material for Dielectric {
-    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
-        ray_debug!("DIELECTRIC - Scattering ray: {:?}", ray);
+// Like interface_direction, for an interface coated with a thin film. The film reflects some colors
+// more than others, so the ray reflects with the average reflectance and is weighed by the color.
+// Transmitted light is what the film does not reflect.
+fn film_direction(film: &ThinFilm, ray: &Ray, hit: &Hit, refractive_index: f32) -> (Vec3, Color) {
+    let (outside, inside) = if hit.front_face {
+        (1., refractive_index)
+    } else {
+        (refractive_index, 1.)
+    };
+
+    let unit_direction = ray.direction.unit();
+    let cos_theta = (-unit_direction).dot(hit.normal).min(1.);
+    let sin_theta = (1. - cos_theta * cos_theta).sqrt();
+
+    // A ray carrying a single wavelength only needs the reflectance at that wavelength
+    let reflectance = match ray.wavelength {
+        Some(wavelength) => {
+            let reflectance =
+                film.reflectance(hit, cos_theta, outside, Complex::real(inside), wavelength);
+
+            Color::new(reflectance, reflectance, reflectance)
+        }
+        None => film.reflectance_rgb(hit, cos_theta, outside, |_| Complex::real(inside)),
+    };
 
-        let ri;
-        let normal = hit.normal;
+    let probability = (reflectance.x() + reflectance.y() + reflectance.z()) / 3.;
+    let cannot_refract = outside / inside * sin_theta > 1.0;
 
-        if hit.front_face {
-            ray_debug!("DIELECTRIC - hit front face");
+    if cannot_refract || probability >= 1. || random_f32(0., 1.) < probability {
+        ray_debug!("DIELECTRIC - reflecting off thin film");
 
-            ri = 1.0 / self.refractive_index;
+        let weight = if cannot_refract {
+            Color::new(1., 1., 1.)
         } else {
-            ray_debug!("DIELECTRIC - hit back face");
+            reflectance / probability
+        };
 
-            ri = self.refractive_index;
-        }
+        (unit_direction.reflect(&hit.normal), weight)
+    } else {
+        ray_debug!("DIELECTRIC - refracting through thin film");
 
-        let unit_direction = ray.direction.unit();
+        (
+            unit_direction.refract(&hit.normal, outside / inside),
+            (Color::new(1., 1., 1.) - reflectance) / (1. - probability),
+        )
+    }
+}
 
-        let cos_theta = (-unit_direction).dot(normal).min(1.);
-        let sin_theta = (1. - cos_theta * cos_theta).sqrt();
+impl Material for Dielectric {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        ray_debug!("DIELECTRIC - Scattering ray: {:?}", ray);
+
+        // A dispersive material refracts each wavelength differently, so from here on the path
+        // carries a single wavelength. The first dispersive event picks it and weighs the path
+        // with its color, later ones reuse it.
+        let (refractive_index, wavelength, spectral_weight) =
+            match (&self.dispersion, ray.wavelength) {
+                (Some(dispersion), Some(wavelength)) => (
+                    dispersion.refractive_index(wavelength),
+                    Some(wavelength),
+                    Color::new(1., 1., 1.),
+                ),
+                (Some(dispersion), None) => {
+                    let wavelength = sample_wavelength();
+
+                    ray_debug!("DIELECTRIC - dispersing at wavelength {}nm", wavelength);
 
-        let cannot_refract = ri * sin_theta > 1.0;
+                    (
+                        dispersion.refractive_index(wavelength),
+                        Some(wavelength),
+                        wavelength_to_rgb(wavelength),
+                    )
+                }
+                (None, wavelength) => (self.refractive_index, wavelength, Color::new(1., 1., 1.)),
+            };
 
-        let direction;
-        if cannot_refract || (self.reflectance(cos_theta, ri) > random_f32(0., 1.)) {
-            ray_debug!("DIELECTRIC - reflecting");
+        let (direction, film_weight) = match &self.thin_film {
+            Some(film) => film_direction(film, ray, hit, refractive_index),
+            None => (
+                interface_direction(ray.direction, hit, refractive_index),
+                Color::new(1., 1., 1.),
+            ),
+        };
 
-            direction = unit_direction.reflect(&normal);
+        // Hitting the back face means the ray travelled through the material to get here
+        let attenuation = if hit.front_face {
+            self.attenuation
         } else {
-            ray_debug!("DIELECTRIC - refracting");
+            self.attenuation * self.transmittance(hit.t * ray.direction.length())
+        };
 
-            direction = unit_direction.refract(&normal, ri);
-        }
+        Some((
+            Ray::new(hit.p, direction).with_wavelength(wavelength),
+            attenuation * spectral_weight * film_weight,
+        ))
+    }
 
-        Some((Ray::new(hit.p, direction), self.attenuation))
+    fn is_dispersive(&self) -> bool {
+        self.dispersion.is_some() || self.thin_film.is_some()
     }
 
     fn describe(&self) -> String {
+        let film = match &self.thin_film {
+            Some(film) => format!(", coated with a {}", film.describe()),
+            None => String::new(),
+        };
+
         format!(
-            "Dielectric material with refractive index {} and attenuation {}",
-            self.refractive_index, self.attenuation,
+            "Dielectric material with refractive index {}, attenuation {} and absorption {}{}",
+            self.refractive_index, self.attenuation, self.absorption, film,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::{Point, Vec3};
+
+    // Ray leaving the material after travelling `distance` inside it, hitting the back face
+    fn exit_after(distance: f32) -> (Ray, Hit) {
+        let ray = Ray::new(Point::origin(), Vec3::new(0., 2., 0.));
+        let t = distance / 2.;
+        let hit = Hit::new(&ray, ray.at(t), Vec3::new(0., 1., 0.), t);
+
+        (ray, hit)
+    }
+
+    #[test]
+    fn it_absorbs_more_light_over_longer_distances() {
+        let glass = Dielectric::new_glass().with_absorption(Color::new(0.5, 0.8, 1.), 1.);
+
+        let (ray, hit) = exit_after(1.);
+        let (_, thin) = glass.scatter(&ray, &hit).unwrap();
+
+        let (ray, hit) = exit_after(2.);
+        let (_, thick) = glass.scatter(&ray, &hit).unwrap();
+
+        assert!((thin - Color::new(0.5, 0.8, 1.)).length() < 1e-5);
+        assert!((thick - Color::new(0.25, 0.64, 1.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_refracts_blue_more_than_red() {
+        let glass = Dielectric::new_diamond();
+
+        // Entering at an angle, steep enough to never reflect
+        let refracted = |wavelength| {
+            let ray = Ray::new(Point::new(-1., 1., 0.), Vec3::new(1., -1., 0.))
+                .with_wavelength(Some(wavelength));
+            let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+            loop {
+                let (scattered, weight) = glass.scatter(&ray, &hit).unwrap();
+
+                if scattered.direction.y() < 0. {
+                    assert_eq!(scattered.wavelength, Some(wavelength));
+                    assert_eq!(weight, Color::new(1., 1., 1.)); // Weighed when the wavelength was picked
+                    return scattered.direction;
+                }
+            }
+        };
+
+        let angle = |direction: Vec3| direction.angle_between(Vec3::new(0., -1., 0.));
+
+        assert!(angle(refracted(450.)) < angle(refracted(650.)));
+    }
+
+    #[test]
+    fn it_picks_a_wavelength_for_white_light() {
+        let glass = Dielectric::new_bk7();
+
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+        let (scattered, weight) = glass.scatter(&ray, &hit).unwrap();
+        let wavelength = scattered.wavelength.unwrap();
+
+        assert_eq!(weight, wavelength_to_rgb(wavelength));
+    }
+
+    #[test]
+    fn it_passes_light_through_a_bubble() {
+        let bubble = Dielectric::new_bubble(ThinFilm::new_soap(400.));
+
+        let ray = Ray::new(Point::new(-1., 1., 0.), Vec3::new(1., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+        let samples = 10000;
+        let (mut reflected, mut transmitted) = (Color::zero(), Color::zero());
+
+        for _ in 0..samples {
+            let (scattered, weight) = bubble.scatter(&ray, &hit).unwrap();
+
+            if scattered.direction.y() > 0. {
+                reflected = reflected + weight;
+            } else {
+                // Both sides of the film are air, the ray carries straight on
+                assert!((scattered.direction - ray.direction.unit()).length() < 1e-4);
+                transmitted = transmitted + weight;
+            }
+        }
+
+        // All light is either reflected or transmitted, a colored reflection leaves the
+        // complementary color to pass through
+        let total = (reflected + transmitted) / samples as f32;
+        assert!((total - Color::new(1., 1., 1.)).length() < 0.02, "{total}");
+        assert!(reflected.length() > 0. && transmitted.length() > 0.);
+    }
+
+    #[test]
+    fn it_reflects_a_single_wavelength_by_the_film_at_that_wavelength() {
+        let film = ThinFilm::new_soap(400.);
+        let bubble = Dielectric::new_bubble(ThinFilm::new_soap(400.));
+
+        // The spectral renderer only follows the hero wavelength through the film
+        assert!(bubble.is_dispersive());
+
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+        let samples = 20000;
+
+        for wavelength in [450., 600.] {
+            let ray = ray.with_wavelength(Some(wavelength));
+            let expected = film.reflectance(&hit, 1., 1., Complex::real(1.), wavelength);
+            let mut reflected = 0.;
+
+            for _ in 0..samples {
+                let (scattered, weight) = bubble.scatter(&ray, &hit).unwrap();
+
+                // A single wavelength has no color, only an intensity
+                assert!(weight.x() == weight.y() && weight.y() == weight.z());
+
+                if scattered.direction.y() > 0. {
+                    reflected += weight.x();
+                }
+            }
+
+            let reflected = reflected / samples as f32;
+            assert!(
+                (reflected - expected).abs() < 0.02,
+                "{reflected} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_does_not_absorb_before_entering() {
+        let glass = Dielectric::new_glass().with_absorption(Color::new(0.5, 0.5, 0.5), 1.);
+
+        let ray = Ray::new(Point::new(0., 5., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 5.);
+
+        let (_, attenuation) = glass.scatter(&ray, &hit).unwrap();
+
+        assert_eq!(attenuation, Color::new(1., 1., 1.));
+    }
+}
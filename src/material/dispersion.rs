@@ -0,0 +1,66 @@
+// Dispersion: the refractive index of a transparent material depends on the wavelength of the
+// light, blue light is bent more than red light. This is what splits white light into a rainbow in
+// a prism and gives diamonds their fire.
+//
+// Both models take the wavelength in micrometers, which is how their coefficients are published.
+#[derive(Clone, Copy, Debug)]
+pub enum Dispersion {
+    // n = a + b / λ², a simple empirical fit that is good enough for most glasses
+    Cauchy { a: f32, b: f32 },
+    // n² = 1 + Σ b_i λ² / (λ² - c_i), accurate across the visible range; glass catalogs list
+    // these coefficients
+    Sellmeier { b: [f32; 3], c: [f32; 3] },
+}
+
+impl Dispersion {
+    // Schott N-BK7, the most common optical glass
+    pub fn bk7() -> Self {
+        Dispersion::Sellmeier {
+            b: [1.039_612, 0.231_792_3, 1.010_469],
+            c: [0.006_000_699, 0.020_017_91, 103.560_7],
+        }
+    }
+
+    pub fn diamond() -> Self {
+        Dispersion::Sellmeier {
+            b: [0.3306, 4.3356, 0.],
+            c: [0.030_625, 0.011_236, 0.],
+        }
+    }
+
+    // Refractive index for light of the given wavelength in nanometers
+    pub fn refractive_index(&self, wavelength: f32) -> f32 {
+        let micrometers = wavelength / 1000.;
+        let l2 = micrometers * micrometers;
+
+        match self {
+            Dispersion::Cauchy { a, b } => a + b / l2,
+            Dispersion::Sellmeier { b, c } => {
+                (1. + (0..3).map(|i| b[i] * l2 / (l2 - c[i])).sum::<f32>()).sqrt()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_matches_published_refractive_indices() {
+        // Indices at the helium d-line (587.6nm), as found in glass catalogs
+        assert!((Dispersion::bk7().refractive_index(587.6) - 1.5168).abs() < 1e-3);
+        assert!((Dispersion::diamond().refractive_index(587.6) - 2.417).abs() < 5e-3);
+    }
+
+    #[test]
+    fn it_bends_blue_more_than_red() {
+        for dispersion in [
+            Dispersion::bk7(),
+            Dispersion::diamond(),
+            Dispersion::Cauchy { a: 1.5, b: 0.005 },
+        ] {
+            assert!(dispersion.refractive_index(450.) > dispersion.refractive_index(650.));
+        }
+    }
+}
@@ -0,0 +1,359 @@
+// Building blocks for microfacet materials. A rough surface is modelled as a huge number of tiny
+// perfectly smooth mirror facets (microfacets) whose orientations follow a statistical
+// distribution: the smoother the surface, the more facets are aligned with the macro surface
+// normal. Light reflects (or refracts) off a single facet, but facets can hide each other from the
+// viewer (masking) or from the light (shadowing).
+//
+// Everything here works in a local shading frame where the surface normal is the z axis, see Onb.
+use crate::math::chance::random_f32;
+use crate::math::constants::PI;
+use crate::math::vector::{Color, Vec3};
+
+// Smallest alpha we allow, perfectly smooth surfaces make the distribution degenerate
+const MIN_ALPHA: f32 = 1e-4;
+
+// GGX (also known as Trowbridge-Reitz) microfacet distribution. Anisotropic surfaces like brushed
+// metal are rougher in one direction than the other, with a separate width along the x axis of
+// the local frame (the surface tangent) and the y axis (the bitangent).
+#[derive(Clone, Copy, Debug)]
+pub struct Ggx {
+    // Width of the distribution: 0 is a perfect mirror, 1 is very rough
+    pub alpha_x: f32,
+    pub alpha_y: f32,
+}
+
+impl Ggx {
+    pub fn new(alpha: f32) -> Self {
+        Self::new_anisotropic(alpha, alpha)
+    }
+
+    pub fn new_anisotropic(alpha_x: f32, alpha_y: f32) -> Self {
+        Self {
+            alpha_x: alpha_x.max(MIN_ALPHA),
+            alpha_y: alpha_y.max(MIN_ALPHA),
+        }
+    }
+
+    // Maps a perceptually linear roughness (as used by artists) onto alpha
+    pub fn from_roughness(roughness: f32) -> Self {
+        Self::new(roughness * roughness)
+    }
+
+    pub fn from_anisotropic_roughness(roughness_x: f32, roughness_y: f32) -> Self {
+        Self::new_anisotropic(roughness_x * roughness_x, roughness_y * roughness_y)
+    }
+
+    // A perfect mirror: all facets face along the normal
+    pub fn is_smooth(&self) -> bool {
+        self.alpha_x <= MIN_ALPHA && self.alpha_y <= MIN_ALPHA
+    }
+
+    // Density of facets oriented along `m`
+    pub fn d(&self, m: Vec3) -> f32 {
+        if m.z() <= 0. {
+            return 0.;
+        }
+
+        let x = m.x() / self.alpha_x;
+        let y = m.y() / self.alpha_y;
+        let denominator = x * x + y * y + m.z() * m.z();
+
+        1. / (PI * self.alpha_x * self.alpha_y * denominator * denominator)
+    }
+
+    // Smith's auxiliary function, relates to the fraction of facets hidden when looking along `w`
+    pub fn lambda(&self, w: Vec3) -> f32 {
+        let cos2 = w.z() * w.z();
+        if cos2 == 0. {
+            return f32::INFINITY;
+        }
+
+        // Squared tangent of the angle with the normal, stretched by the roughness in each direction
+        let x = self.alpha_x * w.x();
+        let y = self.alpha_y * w.y();
+        let alpha2_tan2 = (x * x + y * y) / cos2;
+
+        (-1. + (1. + alpha2_tan2).sqrt()) / 2.
+    }
+
+    // Fraction of facets visible from direction `w` (masking)
+    pub fn g1(&self, w: Vec3) -> f32 {
+        1. / (1. + self.lambda(w))
+    }
+
+    // Fraction of facets visible from both `wo` and `wi` (height-correlated masking-shadowing)
+    pub fn g2(&self, wo: Vec3, wi: Vec3) -> f32 {
+        1. / (1. + self.lambda(wo) + self.lambda(wi))
+    }
+
+    // Samples a facet normal from the facets visible from direction `wo`, following Heitz,
+    // "Sampling the GGX Distribution of Visible Normals" (2018). Sampling only visible facets
+    // avoids wasting samples on facets the viewer can not see.
+    pub fn sample_visible_normal(&self, wo: Vec3) -> Vec3 {
+        // Even the narrowest distribution has tails, a smooth surface reflects like a mirror
+        if self.is_smooth() {
+            return Vec3::new(0., 0., 1.);
+        }
+
+        // Stretch the view direction so the distribution becomes a hemisphere
+        let vh = Vec3::new(self.alpha_x * wo.x(), self.alpha_y * wo.y(), wo.z()).unit();
+
+        // Orthonormal basis around the stretched view direction
+        let length_squared = vh.x() * vh.x() + vh.y() * vh.y();
+        let t1 = if length_squared > 0. {
+            Vec3::new(-vh.y(), vh.x(), 0.) / length_squared.sqrt()
+        } else {
+            Vec3::new(1., 0., 0.)
+        };
+        let t2 = vh.cross(t1);
+
+        // Sample the projected area of the hemisphere
+        let r = random_f32(0., 1.).sqrt();
+        let phi = 2. * PI * random_f32(0., 1.);
+        let p1 = r * phi.cos();
+        let p2 = r * phi.sin();
+        let s = 0.5 * (1. + vh.z());
+        let p2 = (1. - s) * (1. - p1 * p1).max(0.).sqrt() + s * p2;
+
+        // Project back onto the hemisphere and unstretch
+        let nh = p1 * t1 + p2 * t2 + (1. - p1 * p1 - p2 * p2).max(0.).sqrt() * vh;
+
+        Vec3::new(self.alpha_x * nh.x(), self.alpha_y * nh.y(), nh.z().max(0.)).unit()
+    }
+}
+
+// Mirrors `w` around the facet normal `m`
+pub fn reflect(w: Vec3, m: Vec3) -> Vec3 {
+    2. * w.dot(m) * m - w
+}
+
+// Refracts `w` through the facet with normal `m`, both on the same side of the facet. `eta` is the
+// refractive index on the other side of the facet over the one on the side of `w`. Returns None
+// on total internal reflection.
+pub fn refract(w: Vec3, m: Vec3, eta: f32) -> Option<Vec3> {
+    let cos_i = w.dot(m);
+    let sin2_t = (1. - cos_i * cos_i).max(0.) / (eta * eta);
+
+    if sin2_t >= 1. {
+        return None;
+    }
+
+    let cos_t = (1. - sin2_t).sqrt();
+
+    Some(-w / eta + (cos_i / eta - cos_t) * m)
+}
+
+// Exact Fresnel reflectance of a dielectric (glass, water) for unpolarized light arriving at an
+// angle with cosine `cos_i`. `eta` is the refractive index on the other side of the interface over
+// the one on the side the light comes from. Unlike Schlick's approximation this also covers total
+// internal reflection exactly.
+pub fn fresnel_dielectric(cos_i: f32, eta: f32) -> f32 {
+    let cos_i = cos_i.clamp(0., 1.);
+    let sin2_t = (1. - cos_i * cos_i) / (eta * eta);
+
+    if sin2_t >= 1. {
+        return 1.; // Total internal reflection
+    }
+
+    let cos_t = (1. - sin2_t).sqrt();
+
+    let r_parallel = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    let r_perpendicular = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+
+    0.5 * (r_parallel * r_parallel + r_perpendicular * r_perpendicular)
+}
+
+// Schlick's approximation of Fresnel reflectance, from the reflectance `f0` at normal incidence.
+// Cheap, and artists can pick `f0` as a color.
+pub fn fresnel_schlick(cos_theta: f32, f0: Color) -> Color {
+    let weight = (1. - cos_theta.clamp(0., 1.)).powi(5);
+
+    f0 + (Color::new(1., 1., 1.) - f0) * weight
+}
+
+// Fresnel reflectance of a conductor (metal) for light arriving at an angle with cosine
+// `cos_theta`. Metals have a complex refractive index: `eta` is the real part and `k` (the
+// extinction coefficient) describes how quickly light is absorbed inside the metal. Both are
+// given per color channel, which is what gives metals like gold and copper their color.
+pub fn fresnel_conductor(cos_theta: f32, eta: Color, k: Color) -> Color {
+    Color::new(
+        fresnel_conductor_channel(cos_theta, eta.x(), k.x()),
+        fresnel_conductor_channel(cos_theta, eta.y(), k.y()),
+        fresnel_conductor_channel(cos_theta, eta.z(), k.z()),
+    )
+}
+
+// Exact conductor Fresnel equations for unpolarized light, from the outside medium being air
+fn fresnel_conductor_channel(cos_theta: f32, eta: f32, k: f32) -> f32 {
+    let cos2 = cos_theta.clamp(0., 1.).powi(2);
+    let sin2 = 1. - cos2;
+    let eta2 = eta * eta;
+    let k2 = k * k;
+
+    let t0 = eta2 - k2 - sin2;
+    let a2_plus_b2 = (t0 * t0 + 4. * eta2 * k2).sqrt();
+    let a = (0.5 * (a2_plus_b2 + t0)).max(0.).sqrt();
+
+    let t1 = a2_plus_b2 + cos2;
+    let t2 = 2. * cos_theta * a;
+    let r_s = (t1 - t2) / (t1 + t2);
+
+    let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+    let t4 = t2 * sin2;
+    let r_p = r_s * (t3 - t4) / (t3 + t4);
+
+    0.5 * (r_p + r_s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_normalizes_the_distribution() {
+        // The projected area of all facets must add up to the area of the macro surface:
+        // the integral of D(m) cos(theta_m) over the hemisphere is 1
+        for alpha in [0.1, 0.5, 1.] {
+            let ggx = Ggx::new(alpha);
+            let steps = 2000;
+            let d_theta = (PI / 2.) / steps as f32;
+
+            let integral: f32 = (0..steps)
+                .map(|i| {
+                    let theta = (i as f32 + 0.5) * d_theta;
+                    let m = Vec3::new(theta.sin(), 0., theta.cos());
+
+                    ggx.d(m) * theta.cos() * theta.sin() * d_theta * 2. * PI
+                })
+                .sum();
+
+            assert!((integral - 1.).abs() < 0.01, "alpha {alpha}: {integral}");
+        }
+    }
+
+    #[test]
+    fn it_normalizes_the_anisotropic_distribution() {
+        let ggx = Ggx::new_anisotropic(0.2, 0.6);
+        let steps = 500;
+        let d_theta = (PI / 2.) / steps as f32;
+        let d_phi = 2. * PI / steps as f32;
+
+        let mut integral = 0.;
+        for i in 0..steps {
+            let theta = (i as f32 + 0.5) * d_theta;
+
+            for j in 0..steps {
+                let phi = (j as f32 + 0.5) * d_phi;
+                let m = Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                );
+
+                integral += ggx.d(m) * theta.cos() * theta.sin() * d_theta * d_phi;
+            }
+        }
+
+        assert!((integral - 1.).abs() < 0.01, "{integral}");
+    }
+
+    #[test]
+    fn it_spreads_facets_along_the_rougher_direction() {
+        let ggx = Ggx::new_anisotropic(0.5, 0.05);
+        let wo = Vec3::new(0., 0., 1.);
+
+        let (mut spread_x, mut spread_y) = (0., 0.);
+        for _ in 0..1000 {
+            let m = ggx.sample_visible_normal(wo);
+
+            spread_x += m.x().abs();
+            spread_y += m.y().abs();
+        }
+
+        assert!(spread_x > 5. * spread_y);
+    }
+
+    #[test]
+    fn it_masks_more_at_grazing_angles() {
+        let ggx = Ggx::new(0.5);
+
+        let straight = Vec3::new(0., 0., 1.);
+        let grazing = Vec3::new(0.99, 0., 0.141).unit();
+
+        assert_eq!(ggx.g1(straight), 1.);
+        assert!(ggx.g1(grazing) < 0.5);
+        assert!(ggx.g2(straight, grazing) <= ggx.g1(grazing));
+    }
+
+    #[test]
+    fn it_samples_visible_normals() {
+        let ggx = Ggx::new(0.6);
+        let wo = Vec3::new(0.6, 0.2, 0.5).unit();
+
+        for _ in 0..1000 {
+            let m = ggx.sample_visible_normal(wo);
+
+            assert!((m.length() - 1.).abs() < 1e-4);
+            assert!(m.z() >= 0.);
+            assert!(m.dot(wo) >= -1e-4); // Back-facing facets are never visible
+        }
+    }
+
+    #[test]
+    fn it_samples_the_normal_when_smooth() {
+        let ggx = Ggx::from_roughness(0.);
+        let wo = Vec3::new(0.6, 0.2, 0.5).unit();
+
+        assert!(ggx.is_smooth());
+        assert!(!Ggx::from_roughness(0.05).is_smooth());
+
+        for _ in 0..100 {
+            assert_eq!(ggx.sample_visible_normal(wo), Vec3::new(0., 0., 1.));
+        }
+    }
+
+    #[test]
+    fn it_calculates_dielectric_reflectance() {
+        // At normal incidence the reflectance is ((n - 1) / (n + 1))², 4% for glass
+        assert!((fresnel_dielectric(1., 1.5) - 0.04).abs() < 1e-5);
+        assert!((fresnel_dielectric(1., 1. / 1.5) - 0.04).abs() < 1e-5);
+
+        // Grazing angles and total internal reflection reflect everything
+        assert!((fresnel_dielectric(0., 1.5) - 1.).abs() < 1e-5);
+        assert_eq!(fresnel_dielectric(0.5, 1. / 1.5), 1.);
+    }
+
+    #[test]
+    fn it_refracts_following_snells_law() {
+        let m = Vec3::new(0., 0., 1.);
+        let w = Vec3::new(0.5, 0., 0.75f32.sqrt());
+
+        let refracted = refract(w, m, 1.5).unwrap();
+
+        // sin(theta_t) = sin(theta_i) / eta, on the other side of the facet
+        assert!((refracted.length() - 1.).abs() < 1e-5);
+        assert!((-refracted.x() - 0.5 / 1.5).abs() < 1e-5);
+        assert!(refracted.z() < 0.);
+
+        // Too shallow from the dense side: total internal reflection
+        assert!(refract(Vec3::new(0.8, 0., 0.6), m, 1. / 1.5).is_none());
+    }
+
+    #[test]
+    fn it_calculates_conductor_reflectance() {
+        let eta = Color::new(0.2, 0.9, 1.1);
+        let k = Color::new(3.9, 2.5, 2.1);
+
+        // At normal incidence the reflectance is ((n - 1)² + k²) / ((n + 1)² + k²)
+        let expected = |n: f32, k: f32| ((n - 1.).powi(2) + k * k) / ((n + 1.).powi(2) + k * k);
+        let normal = fresnel_conductor(1., eta, k);
+
+        assert!((normal.x() - expected(eta.x(), k.x())).abs() < 1e-5);
+        assert!((normal.y() - expected(eta.y(), k.y())).abs() < 1e-5);
+        assert!((normal.z() - expected(eta.z(), k.z())).abs() < 1e-5);
+
+        // At grazing angles every surface becomes a perfect mirror
+        let grazing = fresnel_conductor(0., eta, k);
+        assert!((grazing - Color::new(1., 1., 1.)).length() < 1e-5);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::math::chance::random_f32;
+use crate::math::vector::Color;
+use crate::scene::object::Hit;
+use crate::texture::base::Texture;
+use crate::texture::solid::SolidColor;
+
+// Blends two materials: every scatter picks one of them at random, the weight is the chance of
+// picking the second one. Over many samples this averages out to the two materials mixed in that
+// proportion, for example a metal with patches of dust on it. A texture as weight varies the mix
+// across the surface.
+pub struct MixMaterial {
+    pub first: Box<dyn Material>,
+    pub second: Box<dyn Material>,
+    pub weight: Box<dyn Texture>, // 0 is only the first material, 1 only the second
+}
+
+impl MixMaterial {
+    pub fn new(first: Box<dyn Material>, second: Box<dyn Material>, weight: f32) -> Self {
+        Self {
+            first,
+            second,
+            weight: Box::new(SolidColor::new_scalar(weight)),
+        }
+    }
+
+    pub fn with_weight_texture(mut self, weight: Box<dyn Texture>) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+impl Material for MixMaterial {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        let weight = self.weight.value_scalar(hit.u, hit.v, hit.p);
+
+        if random_f32(0., 1.) < weight {
+            ray_debug!("MIX - picked second material");
+
+            self.second.scatter(ray, hit)
+        } else {
+            ray_debug!("MIX - picked first material");
+
+            self.first.scatter(ray, hit)
+        }
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.first.is_dispersive() || self.second.is_dispersive()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Mix of ({}) and ({}) by {}",
+            self.first.describe(),
+            self.second.describe(),
+            self.weight.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::{Point, Vec3};
+
+    #[test]
+    fn it_picks_materials_by_weight() {
+        let red = Color::new(1., 0., 0.);
+        let blue = Color::new(0., 0., 1.);
+        let mix = MixMaterial::new(
+            Box::new(Lambert::new(red)),
+            Box::new(Lambert::new(blue)),
+            0.25,
+        );
+
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+        let samples = 10000;
+        let blue_picks = (0..samples)
+            .filter(|_| mix.scatter(&ray, &hit).unwrap().1 == blue)
+            .count();
+
+        assert!((blue_picks as f32 / samples as f32 - 0.25).abs() < 0.02);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::object::Hit;
+use crate::texture::base::Texture;
+
+// Adds surface detail to a material without extra geometry by changing the normal it sees. The
+// normal map is an image in tangent space: red tilts the normal along the tangent (u), green along
+// the bitangent (v) and blue is the part along the original normal, so a flat map is light blue
+// (0.5, 0.5, 1). Load it with ImageTexture::with_linear_data, it holds directions, not colors.
+pub struct NormalMap {
+    pub material: Box<dyn Material>,
+    pub map: Box<dyn Texture>,
+    pub strength: f32, // Scales the tilt, 0 leaves the surface flat
+}
+
+impl NormalMap {
+    pub fn new(material: Box<dyn Material>, map: Box<dyn Texture>) -> Self {
+        Self {
+            material,
+            map,
+            strength: 1.,
+        }
+    }
+
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+impl Material for NormalMap {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        let value = self.map.value(hit.u, hit.v, hit.p);
+        let local_normal = Vec3::new(
+            (2. * value.x() - 1.) * self.strength,
+            (2. * value.y() - 1.) * self.strength,
+            2. * value.z() - 1.,
+        );
+
+        self.material
+            .scatter(ray, &perturb_normal(ray, hit, local_normal))
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.material.is_dispersive()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Normal map {} with strength {} over ({})",
+            self.map.describe(),
+            self.strength,
+            self.material.describe()
+        )
+    }
+}
+
+// Replaces the normal of the hit by `local_normal`, given in the tangent frame of the surface
+// (tangent, bitangent, outward normal). Used by both normal and bump maps.
+pub(crate) fn perturb_normal(ray: &Ray, hit: &Hit, local_normal: Vec3) -> Hit {
+    let (tangent, bitangent, normal) = tangent_frame(hit);
+
+    let perturbed =
+        (tangent * local_normal.x() + bitangent * local_normal.y() + normal * local_normal.z())
+            .unit();
+
+    // Facing the ray like the original normal
+    let perturbed = if hit.front_face {
+        perturbed
+    } else {
+        -perturbed
+    };
+
+    // A strongly tilted normal can end up facing away from the ray, which makes no sense for the
+    // material, keep the real surface normal then
+    if perturbed.dot(ray.direction) >= 0. {
+        return *hit;
+    }
+
+    Hit {
+        normal: perturbed,
+        ..*hit
+    }
+}
+
+// Orthonormal tangent frame around the outward normal, the tangent is kept as close as possible to
+// the hit's tangent
+pub(crate) fn tangent_frame(hit: &Hit) -> (Vec3, Vec3, Vec3) {
+    let normal = hit.outward_normal();
+    let tangent = (hit.tangent - normal * normal.dot(hit.tangent)).unit();
+    let bitangent = normal.cross(tangent);
+
+    (tangent, bitangent, normal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::diffuse::Lambert;
+    use crate::material::testing::ShowNormal;
+    use crate::math::vector::Point;
+    use crate::texture::solid::SolidColor;
+
+    fn hit_from_above() -> (Ray, Hit) {
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.)
+            .with_tangent(Vec3::new(1., 0., 0.));
+
+        (ray, hit)
+    }
+
+    fn seen_normal(map: Color, strength: f32) -> Vec3 {
+        let material = NormalMap::new(Box::new(ShowNormal), Box::new(SolidColor::new(map)))
+            .with_strength(strength);
+        let (ray, hit) = hit_from_above();
+
+        material.scatter(&ray, &hit).unwrap().1
+    }
+
+    #[test]
+    fn it_keeps_the_normal_for_a_flat_map() {
+        let normal = seen_normal(Color::new(0.5, 0.5, 1.), 1.);
+
+        assert!((normal - Vec3::new(0., 1., 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_tilts_the_normal_in_tangent_space() {
+        // Tilted 45° towards the tangent
+        let normal = seen_normal(Color::new(1., 0.5, 1.), 1.);
+        assert!((normal - Vec3::new(1., 1., 0.).unit()).length() < 1e-5);
+
+        // Tilted towards the bitangent, normal x tangent
+        let normal = seen_normal(Color::new(0.5, 1., 1.), 1.);
+        assert!((normal - Vec3::new(0., 1., -1.).unit()).length() < 1e-5);
+
+        // Without strength the tilt disappears
+        let normal = seen_normal(Color::new(1., 0.5, 1.), 0.);
+        assert!((normal - Vec3::new(0., 1., 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_passes_the_hit_on_to_its_material() {
+        let material = NormalMap::new(
+            Box::new(Lambert::new(Color::new(0.2, 0.4, 0.6))),
+            Box::new(SolidColor::new(Color::new(0.5, 0.5, 1.))),
+        );
+        let (ray, hit) = hit_from_above();
+
+        let (_, attenuation) = material.scatter(&ray, &hit).unwrap();
+
+        assert_eq!(attenuation, Color::new(0.2, 0.4, 0.6));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::math::chance::random_f32;
+use crate::math::constants::PI;
+use crate::math::onb::Onb;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::object::Hit;
+
+// Phase functions describe how light scatters inside a participating medium (fog, smoke, ...).
+// They take the role of a surface material for scattering events that happen inside a volume.
+
+// Isotropic phase function: scatters light uniformly into every direction
+pub struct Isotropic {
+    pub albedo: Color, // Fraction of light scattered rather than absorbed at each scattering event
+}
+
+impl Isotropic {
+    pub fn new(albedo: Color) -> Self {
+        Self { albedo }
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(&self, _ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        Some((Ray::new(hit.p, Vec3::random_unit()), self.albedo))
+    }
+
+    fn describe(&self) -> String {
+        format!("Isotropic phase function with albedo {}", self.albedo)
+    }
+}
+
+// Henyey-Greenstein phase function: a one-parameter model of anisotropic scattering. The
+// anisotropy g is the average cosine between the incoming and scattered direction: positive values
+// favour forward scattering (clouds, haze), negative values favour back scattering, 0 is isotropic.
+pub struct HenyeyGreenstein {
+    pub albedo: Color,
+    pub anisotropy: f32, // g, between -1 and 1 (exclusive)
+}
+
+impl HenyeyGreenstein {
+    pub fn new(albedo: Color, anisotropy: f32) -> Self {
+        Self { albedo, anisotropy }
+    }
+
+    // Samples the cosine of the angle between the incoming and the scattered direction by
+    // inverting the phase function's cumulative distribution
+    fn sample_cos_theta(&self) -> f32 {
+        let g = self.anisotropy;
+        let xi = random_f32(0., 1.);
+
+        if g.abs() < 1e-3 {
+            return 1. - 2. * xi; // Isotropic
+        }
+
+        let s = (1. - g * g) / (1. - g + 2. * g * xi);
+
+        ((1. + g * g - s * s) / (2. * g)).clamp(-1., 1.)
+    }
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        let cos_theta = self.sample_cos_theta();
+        let sin_theta = (1. - cos_theta * cos_theta).max(0.).sqrt();
+        let phi = 2. * PI * random_f32(0., 1.);
+
+        // The angle is relative to the direction the ray was travelling in
+        let frame = Onb::from_w(ray.direction.unit());
+        let direction = frame.to_world(Vec3::new(
+            sin_theta * phi.cos(),
+            sin_theta * phi.sin(),
+            cos_theta,
+        ));
+
+        Some((Ray::new(hit.p, direction), self.albedo))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Henyey-Greenstein phase function with albedo {} and anisotropy {}",
+            self.albedo, self.anisotropy
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::Point;
+
+    #[test]
+    fn it_scatters_with_the_configured_average_cosine() {
+        let ray = Ray::new(Point::origin(), Vec3::new(0., 0., 2.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 0., -1.), 0.);
+
+        for g in [-0.5, 0., 0.3, 0.8] {
+            let phase = HenyeyGreenstein::new(Color::new(1., 1., 1.), g);
+
+            let samples = 20000;
+            let total: f32 = (0..samples)
+                .map(|_| {
+                    let (scattered, _) = phase.scatter(&ray, &hit).unwrap();
+                    scattered.direction.unit().dot(Vec3::new(0., 0., 1.))
+                })
+                .sum();
+
+            let mean_cosine = total / samples as f32;
+
+            assert!(
+                (mean_cosine - g).abs() < 0.02,
+                "g {g}: mean cosine {mean_cosine}"
+            );
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::microfacet::{Ggx, fresnel_dielectric, fresnel_schlick, reflect};
+use crate::math::chance::random_f32;
+use crate::math::onb::Onb;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::object::Hit;
+use crate::texture::base::Texture;
+use crate::texture::solid::SolidColor;
+
+// Refractive index of the clear coat, a typical varnish
+const CLEARCOAT_IOR: f32 = 1.5;
+
+// "Principled" material loosely following Disney's (Burley, "Physically Based Shading at Disney",
+// 2012): a handful of intuitive parameters instead of a separate material per kind of surface.
+// From the top down the layers are
+// - an optional clear coat, a thin smooth varnish
+// - a metal, or a dielectric with a specular reflection on top of a diffuse base
+// - sheen, a soft glow at grazing angles like on cloth
+//
+// Each scatter picks a single layer, with the probability of the light reaching and reflecting off
+// that layer (mostly Fresnel). Light a layer does not reflect ends up in the layers below it, so the
+// layers never reflect more light than arrives.
+pub struct Principled {
+    pub base_color: Box<dyn Texture>,
+    pub metallic: Box<dyn Texture>, // 0 is a dielectric, 1 a metal tinted by the base color
+    pub roughness: Box<dyn Texture>, // 0 is a mirror, 1 very rough
+    pub specular: Box<dyn Texture>, // Dielectric reflection, 0.5 is 4% like most materials
+    pub clearcoat: Box<dyn Texture>, // Strength of the clear coat, 0 for none
+    pub clearcoat_roughness: f32,
+    pub sheen: Box<dyn Texture>,
+}
+
+impl Principled {
+    pub fn new(base_color: Color) -> Self {
+        Self {
+            base_color: Box::new(SolidColor::new(base_color)),
+            metallic: Box::new(SolidColor::new_scalar(0.)),
+            roughness: Box::new(SolidColor::new_scalar(0.5)),
+            specular: Box::new(SolidColor::new_scalar(0.5)),
+            clearcoat: Box::new(SolidColor::new_scalar(0.)),
+            clearcoat_roughness: 0.03,
+            sheen: Box::new(SolidColor::new_scalar(0.)),
+        }
+    }
+
+    pub fn with_base_color_texture(mut self, texture: Box<dyn Texture>) -> Self {
+        self.base_color = texture;
+        self
+    }
+
+    pub fn with_metallic(self, metallic: f32) -> Self {
+        self.with_metallic_texture(Box::new(SolidColor::new_scalar(metallic)))
+    }
+
+    pub fn with_metallic_texture(mut self, texture: Box<dyn Texture>) -> Self {
+        self.metallic = texture;
+        self
+    }
+
+    pub fn with_roughness(self, roughness: f32) -> Self {
+        self.with_roughness_texture(Box::new(SolidColor::new_scalar(roughness)))
+    }
+
+    pub fn with_roughness_texture(mut self, texture: Box<dyn Texture>) -> Self {
+        self.roughness = texture;
+        self
+    }
+
+    pub fn with_specular(self, specular: f32) -> Self {
+        self.with_specular_texture(Box::new(SolidColor::new_scalar(specular)))
+    }
+
+    pub fn with_specular_texture(mut self, texture: Box<dyn Texture>) -> Self {
+        self.specular = texture;
+        self
+    }
+
+    pub fn with_clearcoat(self, clearcoat: f32, roughness: f32) -> Self {
+        self.with_clearcoat_texture(Box::new(SolidColor::new_scalar(clearcoat)), roughness)
+    }
+
+    pub fn with_clearcoat_texture(mut self, texture: Box<dyn Texture>, roughness: f32) -> Self {
+        self.clearcoat = texture;
+        self.clearcoat_roughness = roughness;
+        self
+    }
+
+    pub fn with_sheen(self, sheen: f32) -> Self {
+        self.with_sheen_texture(Box::new(SolidColor::new_scalar(sheen)))
+    }
+
+    pub fn with_sheen_texture(mut self, texture: Box<dyn Texture>) -> Self {
+        self.sheen = texture;
+        self
+    }
+
+    // Mirror reflection off a facet picked from the visible facets, weighted like in Conductor
+    fn reflect_off_facet(
+        distribution: &Ggx,
+        wo: Vec3,
+        m: Vec3,
+        frame: &Onb,
+        hit: &Hit,
+        fresnel: Color,
+    ) -> Option<(Ray, Color)> {
+        let wi = reflect(wo, m);
+
+        if wi.z() <= 0. {
+            return None;
+        }
+
+        let shadowing = distribution.g2(wo, wi) / distribution.g1(wo);
+
+        Some((Ray::new(hit.p, frame.to_world(wi)), fresnel * shadowing))
+    }
+}
+
+impl Material for Principled {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        let base_color = self.base_color.value(hit.u, hit.v, hit.p);
+        let metallic = self
+            .metallic
+            .value_scalar(hit.u, hit.v, hit.p)
+            .clamp(0., 1.);
+        let roughness = self
+            .roughness
+            .value_scalar(hit.u, hit.v, hit.p)
+            .clamp(0., 1.);
+        let specular = self
+            .specular
+            .value_scalar(hit.u, hit.v, hit.p)
+            .clamp(0., 1.);
+        let clearcoat = self
+            .clearcoat
+            .value_scalar(hit.u, hit.v, hit.p)
+            .clamp(0., 1.);
+        let sheen = self.sheen.value_scalar(hit.u, hit.v, hit.p).clamp(0., 1.);
+
+        let frame = Onb::from_w(hit.normal);
+        let wo = frame.to_local(-ray.direction.unit());
+
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        let white = Color::new(1., 1., 1.);
+
+        if clearcoat > 0. {
+            let coat = Ggx::from_roughness(self.clearcoat_roughness);
+            let m = coat.sample_visible_normal(wo);
+
+            if random_f32(0., 1.) < clearcoat * fresnel_dielectric(wo.dot(m), CLEARCOAT_IOR) {
+                ray_debug!("PRINCIPLED - reflecting off clear coat");
+
+                return Self::reflect_off_facet(&coat, wo, m, &frame, hit, white);
+            }
+        }
+
+        let distribution = Ggx::from_roughness(roughness);
+        let m = distribution.sample_visible_normal(wo);
+        let cos_om = wo.dot(m);
+
+        if random_f32(0., 1.) < metallic {
+            ray_debug!("PRINCIPLED - reflecting off metal");
+
+            let fresnel = fresnel_schlick(cos_om, base_color);
+
+            return Self::reflect_off_facet(&distribution, wo, m, &frame, hit, fresnel);
+        }
+
+        let specular = fresnel_schlick(cos_om, white * (0.08 * specular)).x();
+
+        if random_f32(0., 1.) < specular {
+            ray_debug!("PRINCIPLED - specular reflection");
+
+            return Self::reflect_off_facet(&distribution, wo, m, &frame, hit, white);
+        }
+
+        ray_debug!("PRINCIPLED - diffuse reflection");
+
+        // Same cosine weighted scatter as Lambert
+        let mut direction = hit.normal + Vec3::random_unit();
+        if direction.near_zero() {
+            direction = hit.normal;
+        }
+
+        // Sheen brightens the diffuse reflection towards white where the light grazes the surface
+        let half = (direction.unit() - ray.direction.unit()).unit();
+        let sheen = sheen * (1. - direction.unit().dot(half).clamp(0., 1.)).powi(5);
+
+        Some((
+            Ray::new(hit.p, direction),
+            base_color * (1. - sheen) + white * sheen,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Principled material with base color {}, metallic {}, roughness {}, specular {}, clear coat {} (roughness {}) and sheen {}",
+            self.base_color.describe(),
+            self.metallic.describe(),
+            self.roughness.describe(),
+            self.specular.describe(),
+            self.clearcoat.describe(),
+            self.clearcoat_roughness,
+            self.sheen.describe(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::Point;
+    use crate::texture::checker::Checker;
+
+    fn hit_from(direction: Vec3, p: Point) -> (Ray, Hit) {
+        let ray = Ray::new(p - direction, direction);
+        let hit = Hit::new(&ray, p, Vec3::new(0., 1., 0.), 1.);
+
+        (ray, hit)
+    }
+
+    fn albedo(material: &Principled, direction: Vec3) -> Color {
+        let (ray, hit) = hit_from(direction, Point::origin());
+        let samples = 20000;
+
+        (0..samples)
+            .filter_map(|_| material.scatter(&ray, &hit))
+            .fold(Color::zero(), |sum, (_, attenuation)| sum + attenuation)
+            / samples as f32
+    }
+
+    // White furnace test: a white material can never reflect more than arrives, however the layers
+    // are mixed. It does lose the light bouncing between facets, which is not simulated. The most
+    // is lost by the white metal at the default roughness of 0.5 (alpha 0.25) from the grazing
+    // direction (cosine 1/sqrt(5)): integrating D * G2 / (4 cos) of GGX over the hemisphere gives a
+    // single scattering albedo of 0.850 there (0.916 head on). The estimate from 20000 samples has a
+    // standard deviation of about 0.002, 0.84 leaves five of those.
+    #[test]
+    fn it_conserves_energy() {
+        let materials = [
+            Principled::new(Color::new(1., 1., 1.)),
+            Principled::new(Color::new(1., 1., 1.)).with_metallic(1.),
+            Principled::new(Color::new(1., 1., 1.))
+                .with_metallic(0.5)
+                .with_roughness(0.2),
+            Principled::new(Color::new(1., 1., 1.))
+                .with_clearcoat(1., 0.1)
+                .with_specular(1.),
+            Principled::new(Color::new(1., 1., 1.)).with_sheen(1.),
+        ];
+
+        for material in materials.iter() {
+            for direction in [Vec3::new(0., -1., 0.), Vec3::new(2., -1., 0.)] {
+                let albedo = albedo(material, direction);
+
+                for channel in [albedo.x(), albedo.y(), albedo.z()] {
+                    assert!(
+                        channel <= 1.01 && channel > 0.84,
+                        "{}: {albedo}",
+                        material.describe()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_mirrors_its_base_color_as_a_smooth_metal() {
+        let gold = Principled::new(Color::new(1., 0.78, 0.34))
+            .with_metallic(1.)
+            .with_roughness(0.);
+        let (ray, hit) = hit_from(Vec3::new(0., -1., 0.), Point::origin());
+
+        let (scattered, attenuation) = gold.scatter(&ray, &hit).unwrap();
+
+        assert!((scattered.direction - Vec3::new(0., 1., 0.)).length() < 1e-2);
+        assert!((attenuation - Color::new(1., 0.78, 0.34)).length() < 1e-2);
+    }
+
+    #[test]
+    fn it_looks_up_its_base_color_texture() {
+        let red = Color::new(1., 0., 0.);
+        let blue = Color::new(0., 0., 1.);
+        let checker = Principled::new(red)
+            .with_specular(0.)
+            .with_base_color_texture(Box::new(Checker::new(
+                1.,
+                Box::new(SolidColor::new(red)),
+                Box::new(SolidColor::new(blue)),
+            )));
+
+        let (ray, hit) = hit_from(Vec3::new(0., -1., 0.), Point::new(0.5, 0., 0.5));
+        assert_eq!(checker.scatter(&ray, &hit).unwrap().1, red);
+
+        let (ray, hit) = hit_from(Vec3::new(0., -1., 0.), Point::new(1.5, 0., 0.5));
+        assert_eq!(checker.scatter(&ray, &hit).unwrap().1, blue);
+    }
+
+    #[test]
+    fn it_looks_up_its_sheen_texture() {
+        let red = Color::new(1., 0., 0.);
+        let velvet_patches = Principled::new(red)
+            .with_specular(0.)
+            .with_sheen_texture(Box::new(Checker::new(
+                1.,
+                Box::new(SolidColor::new_scalar(0.)),
+                Box::new(SolidColor::new_scalar(1.)),
+            )));
+
+        // Without sheen the diffuse reflection keeps the base color, with it turns a bit white
+        let (ray, hit) = hit_from(Vec3::new(0., -1., 0.), Point::new(0.5, 0., 0.5));
+        assert_eq!(velvet_patches.scatter(&ray, &hit).unwrap().1, red);
+
+        let (ray, hit) = hit_from(Vec3::new(0., -1., 0.), Point::new(1.5, 0., 0.5));
+        assert!(velvet_patches.scatter(&ray, &hit).unwrap().1.y() > 0.);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::microfacet::{Ggx, fresnel_dielectric, reflect, refract};
+use crate::math::chance::random_f32;
+use crate::math::onb::Onb;
+use crate::math::vector::Color;
+use crate::scene::object::Hit;
+
+// Rough dielectric like frosted glass or sandblasted acrylic: a GGX microfacet surface where every
+// facet is a smooth interface that reflects or refracts like Dielectric does. Transmitted light is
+// spread out by the facets, which blurs whatever is seen through the material.
+//
+// Reflection and refraction are chosen with the exact Fresnel reflectance of the sampled facet,
+// so the Fresnel term cancels out of the sample weight.
+pub struct RoughDielectric {
+    pub refractive_index: f32,
+    pub roughness: f32,
+    pub attenuation: Color,
+    distribution: Ggx,
+}
+
+impl RoughDielectric {
+    pub fn new(refractive_index: f32, roughness: f32, attenuation: Color) -> Self {
+        Self {
+            refractive_index,
+            roughness,
+            attenuation,
+            distribution: Ggx::from_roughness(roughness),
+        }
+    }
+
+    pub fn new_frosted_glass(roughness: f32) -> Self {
+        Self::new(1.5, roughness, Color::new(1., 1., 1.))
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        // Work in a local frame with the normal along z, wo points back towards where the ray came from
+        let frame = Onb::from_w(hit.normal);
+        let wo = frame.to_local(-ray.direction.unit());
+
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        // Refractive index on the far side of the surface over the one on the ray's side
+        let eta = if hit.front_face {
+            self.refractive_index
+        } else {
+            1. / self.refractive_index
+        };
+
+        let m = self.distribution.sample_visible_normal(wo);
+        let fresnel = fresnel_dielectric(wo.dot(m), eta);
+
+        let wi = if random_f32(0., 1.) < fresnel {
+            ray_debug!("ROUGH DIELECTRIC - reflecting");
+
+            let wi = reflect(wo, m);
+            if wi.z() <= 0. {
+                return None; // Reflected into another facet, multiple scattering is not simulated
+            }
+            wi
+        } else {
+            ray_debug!("ROUGH DIELECTRIC - refracting");
+
+            let wi = refract(wo, m, eta)?;
+            if wi.z() >= 0. {
+                return None; // Refracted back out on the same side, not a valid transmission
+            }
+            wi
+        };
+
+        // As for Conductor, visible normal sampling leaves only the shadowing of the outgoing ray
+        let shadowing = self.distribution.g2(wo, wi) / self.distribution.g1(wo);
+
+        Some((
+            Ray::new(hit.p, frame.to_world(wi)),
+            self.attenuation * shadowing,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Rough dielectric material with refractive index {}, roughness {} and attenuation {}",
+            self.refractive_index, self.roughness, self.attenuation,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::{Point, Vec3};
+
+    fn hit(direction: Vec3, front_face: bool) -> (Ray, Hit) {
+        let ray = Ray::new(Point::origin() - direction, direction);
+        let outward_normal = if front_face {
+            Vec3::new(0., 1., 0.)
+        } else {
+            Vec3::new(0., -1., 0.)
+        };
+
+        let hit = Hit::new(&ray, Point::origin(), outward_normal, 1.);
+
+        (ray, hit)
+    }
+
+    // White furnace test: with a non-absorbing material the average sample weight is the fraction
+    // of light that leaves the surface again. It must never exceed 1 (no energy is created), and
+    // should stay close to it, the only loss being light that scatters between facets. That loss
+    // grows with roughness and is largest for light trying to leave the glass.
+    #[test]
+    fn it_conserves_energy() {
+        let directions = [
+            Vec3::new(0., -1., 0.),
+            Vec3::new(1., -1., 0.),
+            Vec3::new(3., -1., 0.5),
+        ];
+
+        for roughness in [0.1, 0.3, 0.6] {
+            let glass = RoughDielectric::new_frosted_glass(roughness);
+            let minimum = 1. - roughness * roughness; // Rougher surfaces lose more between facets
+
+            for direction in directions {
+                for front_face in [true, false] {
+                    let (ray, hit) = hit(direction, front_face);
+
+                    let samples = 20000;
+                    let total: f32 = (0..samples)
+                        .map(|_| match glass.scatter(&ray, &hit) {
+                            Some((_, attenuation)) => attenuation.x(),
+                            None => 0.,
+                        })
+                        .sum();
+                    let albedo = total / samples as f32;
+
+                    assert!(
+                        albedo <= 1. && albedo > minimum,
+                        "roughness {roughness}, direction {direction}, front face {front_face}: {albedo}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_transmits_straight_through_when_smooth() {
+        let glass = RoughDielectric::new_frosted_glass(0.);
+        let (ray, hit) = hit(Vec3::new(0., -1., 0.), true);
+
+        let mut transmitted = 0;
+
+        for _ in 0..1000 {
+            let (scattered, _) = glass.scatter(&ray, &hit).unwrap();
+            let direction = scattered.direction.unit();
+
+            if direction.y() < 0. {
+                assert!((direction - Vec3::new(0., -1., 0.)).length() < 1e-5);
+                transmitted += 1;
+            } else {
+                assert!((direction - Vec3::new(0., 1., 0.)).length() < 1e-5);
+            }
+        }
+
+        // Head on, glass reflects only 4% of the light
+        assert!(transmitted > 900);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::math::constants::PI;
+use crate::math::onb::Onb;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::object::Hit;
+use std::sync::OnceLock;
+
+// Resolution of the table of sheen albedos, over roughness and the cosine of the view angle
+const ALBEDO_STEPS: usize = 16;
+
+// Smallest roughness we allow, the distribution becomes a spike at grazing angles below it
+const MIN_ROUGHNESS: f32 = 0.05;
+
+// Cloth like velvet or satin: a diffuse base covered in fibers standing up from the surface. Seen
+// from the front the fibers hardly show, but at grazing angles light catches their sides, giving
+// the soft bright rim typical for fabric.
+//
+// The fibers are modeled by the "Charlie" sheen distribution (Estevez and Kulla, "Production
+// Friendly Microfacet Sheen BRDF", 2017) with Ashikhmin's visibility term. Light the fibers reflect
+// does not reach the base, so the base is darkened by the average reflectance of the sheen for the
+// view direction (albedo scaling).
+pub struct Sheen {
+    pub base_color: Color,
+    pub sheen_color: Color,
+    pub roughness: f32, // 0 keeps the sheen to the very edges, 1 spreads it out
+}
+
+impl Sheen {
+    pub fn new(base_color: Color, sheen_color: Color, roughness: f32) -> Self {
+        Self {
+            base_color,
+            sheen_color,
+            roughness: roughness.clamp(MIN_ROUGHNESS, 1.),
+        }
+    }
+
+    // Dark fabric with a bright sheen of the same color
+    pub fn new_velvet(color: Color) -> Self {
+        Self::new(color * 0.5, color, 0.3)
+    }
+
+    // Fraction of light the sheen reflects for a viewer looking from `cos_o`, interpolated from a
+    // table computed on first use
+    fn albedo(&self, cos_o: f32) -> f32 {
+        static TABLE: OnceLock<Vec<f32>> = OnceLock::new();
+
+        let table = TABLE.get_or_init(|| {
+            let at = |i: usize| i as f32 / (ALBEDO_STEPS - 1) as f32;
+
+            (0..ALBEDO_STEPS * ALBEDO_STEPS)
+                .map(|i| {
+                    let roughness = at(i / ALBEDO_STEPS).max(MIN_ROUGHNESS);
+                    let cos_o = at(i % ALBEDO_STEPS).max(1e-3);
+
+                    sheen_albedo(roughness, cos_o)
+                })
+                .collect()
+        });
+
+        // Bilinear interpolation between the four nearest entries
+        let position = |value: f32| {
+            let x = value.clamp(0., 1.) * (ALBEDO_STEPS - 1) as f32;
+            let i = (x as usize).min(ALBEDO_STEPS - 2);
+
+            (i, x - i as f32)
+        };
+        let (r, tr) = position(self.roughness);
+        let (c, tc) = position(cos_o);
+        let entry = |r: usize, c: usize| table[r * ALBEDO_STEPS + c];
+
+        let low = entry(r, c) * (1. - tc) + entry(r, c + 1) * tc;
+        let high = entry(r + 1, c) * (1. - tc) + entry(r + 1, c + 1) * tc;
+
+        low * (1. - tr) + high * tr
+    }
+}
+
+// Charlie distribution of fiber orientations: most fibers stand up, so facets facing sideways
+// (half vectors far from the normal) are most common
+fn charlie(roughness: f32, cos_h: f32) -> f32 {
+    let inverse = 1. / roughness;
+    let sin2 = (1. - cos_h * cos_h).max(0.);
+
+    (2. + inverse) * sin2.powf(0.5 * inverse) / (2. * PI)
+}
+
+// Ashikhmin's visibility term, a smooth stand-in for the shadowing between fibers
+fn visibility(cos_i: f32, cos_o: f32) -> f32 {
+    1. / (4. * (cos_i + cos_o - cos_i * cos_o))
+}
+
+// Sheen BRDF times the cosine of the incoming light, the part of the light from `wi` reflected
+// towards `wo`, both in the local frame
+fn sheen_reflectance(roughness: f32, wo: Vec3, wi: Vec3) -> f32 {
+    let half = (wo + wi).unit();
+
+    charlie(roughness, half.z()) * visibility(wi.z(), wo.z()) * wi.z()
+}
+
+// Integrates the sheen over the hemisphere of incoming light
+fn sheen_albedo(roughness: f32, cos_o: f32) -> f32 {
+    let steps = 64;
+    let wo = Vec3::new((1. - cos_o * cos_o).sqrt(), 0., cos_o);
+    let d_cos = 1. / steps as f32;
+    let d_phi = PI / steps as f32; // Symmetric around the plane of wo, half the circle will do
+
+    let mut albedo = 0.;
+    for i in 0..steps {
+        let cos_i = (i as f32 + 0.5) * d_cos;
+        let sin_i = (1. - cos_i * cos_i).sqrt();
+
+        for j in 0..steps {
+            let phi = (j as f32 + 0.5) * d_phi;
+            let wi = Vec3::new(sin_i * phi.cos(), sin_i * phi.sin(), cos_i);
+
+            albedo += 2. * sheen_reflectance(roughness, wo, wi) * d_cos * d_phi;
+        }
+    }
+
+    albedo.min(1.)
+}
+
+impl Material for Sheen {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        let frame = Onb::from_w(hit.normal);
+        let wo = frame.to_local(-ray.direction.unit());
+
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        // Cosine weighted scatter like Lambert, which covers both the base and the sheen
+        let mut direction = hit.normal + Vec3::random_unit();
+        if direction.near_zero() {
+            direction = hit.normal;
+        }
+
+        let wi = frame.to_local(direction.unit());
+
+        if wi.z() <= 0. {
+            return None;
+        }
+
+        // With cosine weighted sampling the weight of a BRDF f is pi f
+        let sheen = PI * sheen_reflectance(self.roughness, wo, wi) / wi.z();
+        let covered = self
+            .sheen_color
+            .x()
+            .max(self.sheen_color.y())
+            .max(self.sheen_color.z())
+            * self.albedo(wo.z());
+
+        ray_debug!("SHEEN - sheen {}, base covered for {}", sheen, covered);
+
+        Some((
+            Ray::new(hit.p, direction),
+            self.base_color * (1. - covered) + self.sheen_color * sheen,
+        ))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Sheen material with base color {}, sheen color {} and roughness {}",
+            self.base_color, self.sheen_color, self.roughness
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::Point;
+
+    fn average_weight(material: &Sheen, direction: Vec3) -> Color {
+        let ray = Ray::new(Point::origin() - direction, direction);
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+        let samples = 50000;
+
+        (0..samples)
+            .filter_map(|_| material.scatter(&ray, &hit))
+            .fold(Color::zero(), |sum, (_, weight)| sum + weight)
+            / samples as f32
+    }
+
+    #[test]
+    fn it_conserves_energy() {
+        // White furnace: the sheen takes its light from the base, white cloth reflects everything
+        let white = Color::new(1., 1., 1.);
+
+        for roughness in [0.1, 0.5, 1.] {
+            let cloth = Sheen::new(white, white, roughness);
+
+            for direction in [Vec3::new(0., -1., 0.), Vec3::new(3., -1., 0.)] {
+                let albedo = average_weight(&cloth, direction);
+
+                assert!(
+                    (albedo.x() - 1.).abs() < 0.05,
+                    "roughness {roughness}, {direction}: {albedo}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_shines_at_grazing_angles() {
+        let black = Sheen::new(Color::zero(), Color::new(1., 1., 1.), 0.3);
+
+        let head_on = average_weight(&black, Vec3::new(0., -1., 0.));
+        let grazing = average_weight(&black, Vec3::new(5., -1., 0.));
+
+        assert!(grazing.x() > 3. * head_on.x(), "{head_on} vs {grazing}");
+    }
+}
@@ -0,0 +1,199 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::dielectric::interface_direction;
+use crate::math::chance::random_f32;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::object::Hit;
+
+// Subsurface scattering for translucent materials like skin, wax and marble: light enters the
+// object, bounces around inside it for a while and comes out somewhere else. This is what softens
+// their look and makes thin parts glow when lit from behind.
+//
+// Implemented as a random walk through the inside of a closed object. The surface is a smooth
+// dielectric interface like glass. Every time the ray hits the inside of the surface, we check
+// whether it scattered off a particle before getting there: if so, the walk continues from that
+// point in a random direction, otherwise the ray reaches the surface and leaves (or reflects back
+// in). Each step of the walk is a bounce of the path tracer, so very small mean free paths need a
+// high maximum depth.
+pub struct Subsurface {
+    pub albedo: Color, // Fraction of light surviving each scattering event, per channel
+    pub mean_free_path: Color, // Average distance between scattering events, per channel
+    pub refractive_index: f32,
+}
+
+impl Subsurface {
+    pub fn new(albedo: Color, mean_free_path: Color, refractive_index: f32) -> Self {
+        Self {
+            albedo,
+            mean_free_path,
+            refractive_index,
+        }
+    }
+
+    // Red light travels furthest through skin, giving it a warm glow
+    pub fn new_skin() -> Self {
+        Self::new(
+            Color::new(0.98, 0.9, 0.8),
+            Color::new(0.12, 0.08, 0.06),
+            1.4,
+        )
+    }
+
+    pub fn new_wax() -> Self {
+        Self::new(
+            Color::new(0.98, 0.95, 0.85),
+            Color::new(0.1, 0.1, 0.1),
+            1.45,
+        )
+    }
+
+    pub fn new_marble() -> Self {
+        Self::new(
+            Color::new(0.99, 0.99, 0.98),
+            Color::new(0.06, 0.06, 0.06),
+            1.5,
+        )
+    }
+
+    // Extinction coefficient (chance of scattering per unit of distance) for each channel
+    fn extinction(&self) -> [f32; 3] {
+        [
+            1. / self.mean_free_path.x().max(1e-6),
+            1. / self.mean_free_path.y().max(1e-6),
+            1. / self.mean_free_path.z().max(1e-6),
+        ]
+    }
+}
+
+impl Material for Subsurface {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        if hit.front_face {
+            ray_debug!("SUBSURFACE - entering or reflecting off the surface");
+
+            let direction = interface_direction(ray.direction, hit, self.refractive_index);
+
+            return Some((Ray::new(hit.p, direction), Color::new(1., 1., 1.)));
+        }
+
+        // Inside the object: each channel scatters at its own rate, so the distance is sampled for
+        // a channel picked at random. Weighing by the average chance over all channels keeps the
+        // result right for every one of them.
+        let extinction = self.extinction();
+        let distance = hit.t * ray.direction.length();
+
+        let channel = (random_f32(0., 3.) as usize).min(2);
+        let scatter_distance = -(1. - random_f32(0., 1.)).ln() / extinction[channel];
+
+        if scatter_distance < distance {
+            ray_debug!("SUBSURFACE - scattering inside after {}", scatter_distance);
+
+            let density = extinction.map(|sigma| sigma * (-sigma * scatter_distance).exp());
+            let pdf = density.iter().sum::<f32>() / 3.;
+            let albedo = [self.albedo.x(), self.albedo.y(), self.albedo.z()];
+
+            let weight = Color::new(
+                albedo[0] * density[0] / pdf,
+                albedo[1] * density[1] / pdf,
+                albedo[2] * density[2] / pdf,
+            );
+
+            let p = ray.origin + ray.direction.unit() * scatter_distance;
+
+            return Some((Ray::new(p, Vec3::random_unit()), weight));
+        }
+
+        ray_debug!("SUBSURFACE - reached the surface from the inside");
+
+        let transmittance = extinction.map(|sigma| (-sigma * distance).exp());
+        let probability = transmittance.iter().sum::<f32>() / 3.;
+
+        let weight = Color::new(
+            transmittance[0] / probability,
+            transmittance[1] / probability,
+            transmittance[2] / probability,
+        );
+
+        let direction = interface_direction(ray.direction, hit, self.refractive_index);
+
+        Some((Ray::new(hit.p, direction), weight))
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Subsurface material with albedo {}, mean free path {} and refractive index {}",
+            self.albedo, self.mean_free_path, self.refractive_index
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::Point;
+
+    // Ray that travelled `distance` through the inside of the object to reach its surface
+    fn inside(distance: f32) -> (Ray, Hit) {
+        let ray = Ray::new(Point::origin(), Vec3::new(0., 2., 0.));
+        let t = distance / 2.;
+        let hit = Hit::new(&ray, ray.at(t), Vec3::new(0., 1., 0.), t);
+
+        (ray, hit)
+    }
+
+    #[test]
+    fn it_enters_through_the_surface() {
+        let wax = Subsurface::new_wax();
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+
+        let (scattered, weight) = wax.scatter(&ray, &hit).unwrap();
+
+        assert_eq!(scattered.origin, Point::origin());
+        assert_eq!(weight, Color::new(1., 1., 1.));
+    }
+
+    #[test]
+    fn it_scatters_inside_before_reaching_the_surface() {
+        let material =
+            Subsurface::new(Color::new(0.8, 0.8, 0.8), Color::new(1e-3, 1e-3, 1e-3), 1.5);
+        let (ray, hit) = inside(10.);
+
+        let (scattered, weight) = material.scatter(&ray, &hit).unwrap();
+
+        // Scattered somewhere along the way, with the single scattering albedo as weight
+        assert!(scattered.origin.y() > 0. && scattered.origin.y() < 10.);
+        assert!((weight - Color::new(0.8, 0.8, 0.8)).length() < 1e-4);
+    }
+
+    #[test]
+    fn it_passes_through_thin_parts() {
+        let material = Subsurface::new(Color::new(0.8, 0.8, 0.8), Color::new(1e6, 1e6, 1e6), 1.5);
+        let (ray, hit) = inside(0.1);
+
+        let (scattered, weight) = material.scatter(&ray, &hit).unwrap();
+
+        assert_eq!(scattered.origin, hit.p);
+        assert!((weight - Color::new(1., 1., 1.)).length() < 1e-4);
+    }
+
+    #[test]
+    fn it_attenuates_each_channel_by_its_own_mean_free_path() {
+        // On average, the light reaching the surface without scattering is e^(-distance / mfp)
+        let mean_free_path = Color::new(2., 1., 0.5);
+        let material = Subsurface::new(Color::new(1., 1., 1.), mean_free_path, 1.5);
+        let (ray, hit) = inside(1.);
+
+        let samples = 50000;
+        let reached = (0..samples)
+            .filter_map(|_| material.scatter(&ray, &hit))
+            .filter(|(scattered, _)| scattered.origin == hit.p)
+            .fold(Color::zero(), |sum, (_, weight)| sum + weight)
+            / samples as f32;
+
+        let expected = Color::new((-0.5f32).exp(), (-1f32).exp(), (-2f32).exp());
+        assert!(
+            (reached - expected).length() < 0.02,
+            "{reached} vs {expected}"
+        );
+    }
+}
@@ -0,0 +1,18 @@
+// Materials that help testing the materials wrapping them
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::math::vector::Color;
+use crate::scene::object::Hit;
+
+// Material that does not scatter but reflects the normal it gets back as its color
+pub struct ShowNormal;
+
+impl Material for ShowNormal {
+    fn scatter(&self, ray: &Ray, hit: &Hit) -> Option<(Ray, Color)> {
+        Some((*ray, hit.normal))
+    }
+
+    fn describe(&self) -> String {
+        String::from("Shows the normal")
+    }
+}
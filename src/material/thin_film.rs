@@ -0,0 +1,209 @@
+use crate::math::complex::Complex;
+use crate::math::constants::PI;
+use crate::math::spectrum::{WAVELENGTH_MAX, WAVELENGTH_MIN, wavelength_to_rgb};
+use crate::math::vector::Color;
+use crate::scene::object::Hit;
+use crate::texture::base::Texture;
+use crate::texture::solid::SolidColor;
+
+// Wavelengths the reflectance is evaluated at to find its color. Interference bands get narrower
+// the thicker the film, thick films need more of them.
+const WAVELENGTH_SAMPLES: usize = 32;
+
+// Thin transparent coating on a surface, like a soap film, oil on water or the oxide layer on
+// anodized titanium. Light reflecting off the top of the film interferes with light reflecting off
+// the surface below it. Depending on the thickness of the film and the angle, some wavelengths
+// cancel out and others add up, giving the iridescent colors that shift across the surface.
+pub struct ThinFilm {
+    pub thickness: f32, // In nanometers, colors show for films up to a micrometer or so
+    pub refractive_index: f32,
+    // Scales the thickness across the surface, real films are never exactly even
+    pub variation: Box<dyn Texture>,
+}
+
+impl ThinFilm {
+    pub fn new(thickness: f32, refractive_index: f32) -> Self {
+        Self {
+            thickness,
+            refractive_index,
+            variation: Box::new(SolidColor::new_scalar(1.)),
+        }
+    }
+
+    // Soap film: water with a little soap in it
+    pub fn new_soap(thickness: f32) -> Self {
+        Self::new(thickness, 1.33)
+    }
+
+    pub fn with_variation(mut self, texture: Box<dyn Texture>) -> Self {
+        self.variation = texture;
+        self
+    }
+
+    pub fn thickness_at(&self, hit: &Hit) -> f32 {
+        self.thickness * self.variation.value_scalar(hit.u, hit.v, hit.p).max(0.)
+    }
+
+    // Reflectance at a single wavelength, for light arriving at an angle with cosine `cos_i` from
+    // a medium with refractive index `outside` onto the film on top of a material with (complex)
+    // refractive index `inside`
+    pub fn reflectance(
+        &self,
+        hit: &Hit,
+        cos_i: f32,
+        outside: f32,
+        inside: Complex,
+        wavelength: f32,
+    ) -> f32 {
+        film_reflectance(
+            cos_i,
+            outside,
+            self.refractive_index,
+            inside,
+            self.thickness_at(hit),
+            wavelength,
+        )
+    }
+
+    // Color of the reflected light, from the reflectance at wavelengths across the visible range.
+    // The refractive index of the material below the film is given per wavelength.
+    pub fn reflectance_rgb(
+        &self,
+        hit: &Hit,
+        cos_i: f32,
+        outside: f32,
+        inside: impl Fn(f32) -> Complex,
+    ) -> Color {
+        let thickness = self.thickness_at(hit);
+        let step = (WAVELENGTH_MAX - WAVELENGTH_MIN) / WAVELENGTH_SAMPLES as f32;
+
+        (0..WAVELENGTH_SAMPLES)
+            .map(|i| {
+                let wavelength = WAVELENGTH_MIN + (i as f32 + 0.5) * step;
+                let reflectance = film_reflectance(
+                    cos_i,
+                    outside,
+                    self.refractive_index,
+                    inside(wavelength),
+                    thickness,
+                    wavelength,
+                );
+
+                wavelength_to_rgb(wavelength) * reflectance
+            })
+            .fold(Color::zero(), |sum, rgb| sum + rgb)
+            / WAVELENGTH_SAMPLES as f32
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "thin film of {}nm ({}) with refractive index {}",
+            self.thickness,
+            self.variation.describe(),
+            self.refractive_index
+        )
+    }
+}
+
+// Airy's formula for the reflectance of a film between two media: the sum of all the light
+// bouncing back and forth inside the film, each bounce shifted in phase by the extra distance it
+// travelled. Complex refractive indices take care of absorbing materials below the film (metals)
+// and of total internal reflection, which both turn angles complex.
+fn film_reflectance(
+    cos_i: f32,
+    outside: f32,
+    film: f32,
+    inside: Complex,
+    thickness: f32,
+    wavelength: f32,
+) -> f32 {
+    let cos_i = cos_i.clamp(0., 1.);
+    let n0 = Complex::real(outside);
+    let n1 = Complex::real(film);
+    let n2 = inside;
+
+    // n cos(theta) in each medium, following Snell's law: n0 sin(theta0) = n sin(theta)
+    let sin2 = Complex::real(outside * outside * (1. - cos_i * cos_i));
+    let q0 = Complex::real(outside * cos_i);
+    let q1 = (n1 * n1 - sin2).sqrt();
+    let q2 = (n2 * n2 - sin2).sqrt();
+
+    // Phase difference picked up by a round trip through the film
+    let phase = (Complex::new(0., 4. * PI * thickness / wavelength) * q1).exp();
+
+    let airy = |r01: Complex, r12: Complex| {
+        let r = (r01 + r12 * phase) / (Complex::real(1.) + r01 * r12 * phase);
+
+        r.norm_sqr().min(1.)
+    };
+
+    // Perpendicular and parallel polarized light reflect differently, unpolarized light is half
+    // of each
+    let perpendicular = airy((q0 - q1) / (q0 + q1), (q1 - q2) / (q1 + q2));
+    let parallel = airy(
+        (n1 * n1 * q0 - n0 * n0 * q1) / (n1 * n1 * q0 + n0 * n0 * q1),
+        (n2 * n2 * q1 - n1 * n1 * q2) / (n2 * n2 * q1 + n1 * n1 * q2),
+    );
+
+    0.5 * (perpendicular + parallel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::ray::Ray;
+    use crate::material::microfacet::{fresnel_conductor, fresnel_dielectric};
+    use crate::math::vector::{Point, Vec3};
+
+    fn film_reflectance_at(cos_i: f32, film: f32, inside: Complex, thickness: f32) -> f32 {
+        film_reflectance(cos_i, 1., film, inside, thickness, 550.)
+    }
+
+    #[test]
+    fn it_reflects_like_the_bare_surface_without_a_film() {
+        for cos_i in [1., 0.7, 0.2] {
+            let glass = film_reflectance_at(cos_i, 1.33, Complex::real(1.5), 0.);
+            assert!((glass - fresnel_dielectric(cos_i, 1.5)).abs() < 1e-4);
+
+            let metal = film_reflectance_at(cos_i, 1.33, Complex::new(0.2, 3.9), 0.);
+            let expected =
+                fresnel_conductor(cos_i, Color::new(0.2, 0.2, 0.2), Color::new(3.9, 3.9, 3.9));
+            assert!((metal - expected.x()).abs() < 1e-3, "{metal} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn it_cancels_reflections_with_a_quarter_wave_coating() {
+        // Anti-reflection coating on glass: reflections off the top and bottom of the film cancel
+        let film = 1.5f32.sqrt();
+        let thickness = 550. / (4. * film);
+
+        assert!(film_reflectance_at(1., film, Complex::real(1.5), thickness) < 1e-4);
+        assert!(film_reflectance_at(1., film, Complex::real(1.5), 2. * thickness) > 0.03);
+    }
+
+    #[test]
+    fn it_colors_reflections_by_thickness() {
+        let ray = Ray::new(Point::new(0., 1., 0.), Vec3::new(0., -1., 0.));
+        let hit = Hit::new(&ray, Point::origin(), Vec3::new(0., 1., 0.), 1.);
+        let color = |thickness| {
+            ThinFilm::new_soap(thickness).reflectance_rgb(&hit, 1., 1., |_| Complex::real(1.))
+        };
+
+        let thin = color(200.);
+        let thick = color(300.);
+
+        // Different thicknesses reflect different colors, but never more than all of the light
+        assert!((thin - thick).length() > 0.01, "{thin} vs {thick}");
+        for channel in [
+            thin.x(),
+            thin.y(),
+            thin.z(),
+            thick.x(),
+            thick.y(),
+            thick.z(),
+        ] {
+            assert!((0. ..=1.).contains(&channel));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+// Just enough complex arithmetic for wave optics, where light absorbed by a material or
+// reflecting between layers is described by complex refractive indices and phases
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+
+    pub fn real(re: f32) -> Self {
+        Self::new(re, 0.)
+    }
+
+    // Squared magnitude, the fraction of energy carried by a complex amplitude
+    pub fn norm_sqr(&self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    // Principal square root, never with a negative real part
+    pub fn sqrt(&self) -> Self {
+        let norm = self.norm_sqr().sqrt();
+        let re = (0.5 * (norm + self.re)).max(0.).sqrt();
+        let im = (0.5 * (norm - self.re)).max(0.).sqrt();
+
+        Self::new(re, if self.im < 0. { -im } else { im })
+    }
+
+    pub fn exp(&self) -> Self {
+        let magnitude = self.re.exp();
+
+        Self::new(magnitude * self.im.cos(), magnitude * self.im.sin())
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Mul<f32> for Complex {
+    type Output = Self;
+
+    fn mul(self, other: f32) -> Self {
+        Self::new(self.re * other, self.im * other)
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let denominator = other.norm_sqr();
+
+        Self::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::constants::PI;
+
+    #[test]
+    fn it_takes_square_roots() {
+        assert_eq!(Complex::real(4.).sqrt(), Complex::real(2.));
+        assert_eq!(Complex::real(-4.).sqrt(), Complex::new(0., 2.));
+
+        let root = Complex::new(3., -4.).sqrt();
+        assert!((root * root - Complex::new(3., -4.)).norm_sqr() < 1e-10);
+    }
+
+    #[test]
+    fn it_follows_eulers_formula() {
+        let minus_one = Complex::new(0., PI).exp();
+
+        assert!((minus_one - Complex::real(-1.)).norm_sqr() < 1e-10);
+    }
+
+    #[test]
+    fn it_divides() {
+        let a = Complex::new(1., 2.);
+        let b = Complex::new(-3., 0.5);
+
+        assert!(((a * b) / b - a).norm_sqr() < 1e-10);
+    }
+}
@@ -0,0 +1,102 @@
+use super::vector::Vec3;
+
+// Orthonormal basis: three perpendicular unit vectors, with `w` pointing along a chosen direction
+// (usually a surface normal). Lets us sample directions in a simple local frame where w is "up"
+// and then express them in world space.
+#[derive(Clone, Copy, Debug)]
+pub struct Onb {
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl Onb {
+    // Builds a basis around `w`, which must be unit length. Uses the branchless construction from
+    // Duff et al., "Building an Orthonormal Basis, Revisited" (2017).
+    pub fn from_w(w: Vec3) -> Self {
+        let sign = 1f32.copysign(w.z());
+        let a = -1. / (sign + w.z());
+        let b = w.x() * w.y() * a;
+
+        let u = Vec3::new(1. + sign * w.x() * w.x() * a, sign * b, -sign * w.x());
+        let v = Vec3::new(b, sign + w.y() * w.y() * a, -w.y());
+
+        Self { u, v, w }
+    }
+
+    // Builds a basis around `w` with `u` as close as possible to `towards`, e.g. so the local x axis
+    // follows a surface tangent. Falls back to any basis when `towards` is along `w`.
+    pub fn from_w_towards(w: Vec3, towards: Vec3) -> Self {
+        let u = towards - w * w.dot(towards);
+
+        if u.near_zero() {
+            return Self::from_w(w);
+        }
+
+        let u = u.unit();
+
+        Self {
+            u,
+            v: w.cross(u),
+            w,
+        }
+    }
+
+    // Local coordinates -> world space
+    pub fn to_world(&self, local: Vec3) -> Vec3 {
+        local.x() * self.u + local.y() * self.v + local.z() * self.w
+    }
+
+    // World space -> local coordinates
+    pub fn to_local(&self, world: Vec3) -> Vec3 {
+        Vec3::new(world.dot(self.u), world.dot(self.v), world.dot(self.w))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_an_orthonormal_basis() {
+        let directions = [
+            Vec3::new(0., 0., 1.),
+            Vec3::new(0., 0., -1.),
+            Vec3::new(0., 1., 0.),
+            Vec3::new(1., 2., -3.).unit(),
+        ];
+
+        for w in directions {
+            let onb = Onb::from_w(w);
+
+            assert!((onb.u.length() - 1.).abs() < 1e-5);
+            assert!((onb.v.length() - 1.).abs() < 1e-5);
+            assert!(onb.u.dot(onb.v).abs() < 1e-5);
+            assert!(onb.u.dot(onb.w).abs() < 1e-5);
+            assert!(onb.v.dot(onb.w).abs() < 1e-5);
+            assert!((onb.u.cross(onb.v) - onb.w).length() < 1e-5); // Right-handed
+        }
+    }
+
+    #[test]
+    fn it_aligns_the_basis_with_a_tangent() {
+        let w = Vec3::new(0., 1., 0.);
+        let onb = Onb::from_w_towards(w, Vec3::new(1., 0.5, 0.));
+
+        assert!((onb.u - Vec3::new(1., 0., 0.)).length() < 1e-5);
+        assert!((onb.u.cross(onb.v) - onb.w).length() < 1e-5);
+
+        // No tangent to follow
+        let onb = Onb::from_w_towards(w, w);
+        assert!(onb.u.dot(w).abs() < 1e-5);
+    }
+
+    #[test]
+    fn it_converts_between_local_and_world_space() {
+        let onb = Onb::from_w(Vec3::new(1., 1., 0.).unit());
+        let world = Vec3::new(0.3, -0.2, 0.9);
+
+        assert!((onb.to_world(onb.to_local(world)) - world).length() < 1e-5);
+        assert!((onb.to_world(Vec3::new(0., 0., 1.)) - onb.w).length() < 1e-5);
+    }
+}
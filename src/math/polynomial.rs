@@ -0,0 +1,228 @@
+// Closed form polynomial root finding, following Jochen Schwarze's "Cubic and Quartic Roots" from
+// Graphics Gems I. Coefficients are passed lowest degree first: `[c0, c1, c2]` is c2x² + c1x + c0.
+//
+// Everything is calculated in f64, the quartic in particular (needed to intersect a torus) is very
+// sensitive to rounding errors and falls apart quickly in single precision.
+
+const EQN_EPS: f64 = 1e-9;
+
+fn is_zero(x: f64) -> bool {
+    x > -EQN_EPS && x < EQN_EPS
+}
+
+// Real roots of c2x² + c1x + c0 = 0
+pub fn solve_quadratic(c: [f64; 3]) -> Vec<f64> {
+    // Normal form: x² + px + q = 0
+    let p = c[1] / (2. * c[2]);
+    let q = c[0] / c[2];
+
+    let d = p * p - q;
+
+    if is_zero(d) {
+        vec![-p]
+    } else if d < 0. {
+        vec![]
+    } else {
+        let sqrt_d = d.sqrt();
+        vec![sqrt_d - p, -sqrt_d - p]
+    }
+}
+
+// Real roots of c3x³ + c2x² + c1x + c0 = 0
+pub fn solve_cubic(c: [f64; 4]) -> Vec<f64> {
+    // Normal form: x³ + Ax² + Bx + C = 0
+    let a = c[2] / c[3];
+    let b = c[1] / c[3];
+    let c = c[0] / c[3];
+
+    // Substitute x = y - A/3 to eliminate the quadratic term: y³ + 3py + 2q = 0
+    let sq_a = a * a;
+    let p = 1. / 3. * (-1. / 3. * sq_a + b);
+    let q = 1. / 2. * (2. / 27. * a * sq_a - 1. / 3. * a * b + c);
+
+    // Use Cardano's formula
+    let cb_p = p * p * p;
+    let d = q * q + cb_p;
+
+    let mut roots = if is_zero(d) {
+        if is_zero(q) {
+            vec![0.] // One triple solution
+        } else {
+            let u = (-q).cbrt();
+            vec![2. * u, -u] // One single and one double solution
+        }
+    } else if d < 0. {
+        // Casus irreducibilis: three real solutions
+        let phi = 1. / 3. * (-q / (-cb_p).sqrt()).acos();
+        let t = 2. * (-p).sqrt();
+
+        vec![
+            t * phi.cos(),
+            -t * (phi + std::f64::consts::PI / 3.).cos(),
+            -t * (phi - std::f64::consts::PI / 3.).cos(),
+        ]
+    } else {
+        // One real solution
+        let sqrt_d = d.sqrt();
+        let u = (sqrt_d - q).cbrt();
+        let v = -(sqrt_d + q).cbrt();
+
+        vec![u + v]
+    };
+
+    // Resubstitute
+    let sub = 1. / 3. * a;
+    for root in roots.iter_mut() {
+        *root -= sub;
+    }
+
+    roots
+}
+
+// Real roots of c4x⁴ + c3x³ + c2x² + c1x + c0 = 0
+pub fn solve_quartic(c: [f64; 5]) -> Vec<f64> {
+    // Normal form: x⁴ + Ax³ + Bx² + Cx + D = 0
+    let a = c[3] / c[4];
+    let b = c[2] / c[4];
+    let c_ = c[1] / c[4];
+    let d = c[0] / c[4];
+
+    // Substitute x = y - A/4 to eliminate the cubic term: y⁴ + py² + qy + r = 0
+    let sq_a = a * a;
+    let p = -3. / 8. * sq_a + b;
+    let q = 1. / 8. * sq_a * a - 1. / 2. * a * b + c_;
+    let r = -3. / 256. * sq_a * sq_a + 1. / 16. * sq_a * b - 1. / 4. * a * c_ + d;
+
+    let mut roots = if is_zero(r) {
+        // No absolute term: y(y³ + py + q) = 0
+        let mut roots = solve_cubic([q, p, 0., 1.]);
+        roots.push(0.);
+        roots
+    } else {
+        // Solve the resolvent cubic and use one of its roots to split the quartic into two quadratics
+        let z = solve_cubic([1. / 2. * r * p - 1. / 8. * q * q, -r, -1. / 2. * p, 1.])[0];
+
+        let u = z * z - r;
+        let v = 2. * z - p;
+
+        let u = if is_zero(u) {
+            0.
+        } else if u > 0. {
+            u.sqrt()
+        } else {
+            return vec![];
+        };
+
+        let v = if is_zero(v) {
+            0.
+        } else if v > 0. {
+            v.sqrt()
+        } else {
+            return vec![];
+        };
+
+        let mut roots = solve_quadratic([z - u, if q < 0. { -v } else { v }, 1.]);
+        roots.extend(solve_quadratic([z + u, if q < 0. { v } else { -v }, 1.]));
+        roots
+    };
+
+    // Resubstitute
+    let sub = 1. / 4. * a;
+    for root in roots.iter_mut() {
+        *root -= sub;
+    }
+
+    roots
+}
+
+// Evaluates the polynomial with coefficients `c` (lowest degree first) at x
+pub fn evaluate(c: &[f64], x: f64) -> f64 {
+    c.iter()
+        .rev()
+        .fold(0., |acc, coefficient| acc * x + coefficient)
+}
+
+// Evaluates the derivative of the polynomial with coefficients `c` (lowest degree first) at x
+pub fn evaluate_derivative(c: &[f64], x: f64) -> f64 {
+    c.iter()
+        .enumerate()
+        .skip(1)
+        .rev()
+        .fold(0., |acc, (power, coefficient)| {
+            acc * x + power as f64 * coefficient
+        })
+}
+
+// Polishes a root found by one of the closed form solvers with a few Newton-Raphson iterations
+pub fn refine_root(c: &[f64], mut x: f64, iterations: u32) -> f64 {
+    for _ in 0..iterations {
+        let derivative = evaluate_derivative(c, x);
+        if derivative == 0. {
+            break;
+        }
+
+        x -= evaluate(c, x) / derivative;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut roots: Vec<f64>) -> Vec<f64> {
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        roots
+    }
+
+    fn assert_roots(actual: Vec<f64>, expected: &[f64]) {
+        let actual = sorted(actual);
+
+        assert_eq!(actual.len(), expected.len(), "roots found: {actual:?}");
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-6, "expected {e}, got {a}");
+        }
+    }
+
+    #[test]
+    fn it_solves_quadratics() {
+        assert_roots(solve_quadratic([-6., 1., 1.]), &[-3., 2.]); // (x + 3)(x - 2)
+        assert_roots(solve_quadratic([1., -2., 1.]), &[1.]); // (x - 1)²
+        assert_roots(solve_quadratic([1., 0., 1.]), &[]); // x² + 1
+    }
+
+    #[test]
+    fn it_solves_cubics() {
+        assert_roots(solve_cubic([-6., 11., -6., 1.]), &[1., 2., 3.]); // (x - 1)(x - 2)(x - 3)
+        assert_roots(solve_cubic([-1., 0., 0., 1.]), &[1.]); // x³ - 1
+        assert_roots(solve_cubic([-8., 0., 0., 2.]), &[(4f64).cbrt()]); // 2x³ - 8
+    }
+
+    #[test]
+    fn it_solves_quartics() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        assert_roots(solve_quartic([24., -50., 35., -10., 1.]), &[1., 2., 3., 4.]);
+        // (x² + 1)(x - 1)(x + 2)
+        assert_roots(solve_quartic([-2., 1., -1., 1., 1.]), &[-2., 1.]);
+        // x⁴ + 1
+        assert_roots(solve_quartic([1., 0., 0., 0., 1.]), &[]);
+    }
+
+    #[test]
+    fn it_evaluates_polynomials_and_derivatives() {
+        let c = [24., -50., 35., -10., 1.];
+
+        assert_eq!(evaluate(&c, 2.), 0.);
+        assert_eq!(evaluate(&c, 0.), 24.);
+        assert_eq!(evaluate_derivative(&c, 0.), -50.);
+        assert_eq!(evaluate_derivative(&c, 1.), -6.);
+    }
+
+    #[test]
+    fn it_refines_roots() {
+        let c = [24., -50., 35., -10., 1.];
+
+        assert!((refine_root(&c, 2.9, 10) - 3.).abs() < 1e-12);
+    }
+}
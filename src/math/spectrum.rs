@@ -0,0 +1,285 @@
+use super::chance::random_f32;
+use super::vector::{Color, Vec3};
+use std::ops::Mul;
+use std::sync::OnceLock;
+
+// Range of visible wavelengths in nanometers we trace light for
+pub const WAVELENGTH_MIN: f32 = 380.;
+pub const WAVELENGTH_MAX: f32 = 780.;
+
+// Picks a visible wavelength uniformly at random
+pub fn sample_wavelength() -> f32 {
+    random_f32(WAVELENGTH_MIN, WAVELENGTH_MAX)
+}
+
+// CIE 1931 color matching functions: how strongly light of a single wavelength excites the X, Y
+// and Z responses of a standard observer. Uses the multi-lobe analytic fit by Wyman, Sloan and
+// Shirley, "Simple Analytic Approximations to the CIE XYZ Color Matching Functions" (2013), which
+// saves shipping the tabulated data.
+pub fn wavelength_to_xyz(wavelength: f32) -> Vec3 {
+    // Gaussian with a different width on either side of its peak
+    let lobe = |mu: f32, sigma_below: f32, sigma_above: f32| {
+        let sigma = if wavelength < mu {
+            sigma_below
+        } else {
+            sigma_above
+        };
+        let t = (wavelength - mu) / sigma;
+
+        (-0.5 * t * t).exp()
+    };
+
+    Vec3::new(
+        1.056 * lobe(599.8, 37.9, 31.0) + 0.362 * lobe(442.0, 16.0, 26.7)
+            - 0.065 * lobe(501.1, 20.4, 26.2),
+        0.821 * lobe(568.8, 46.9, 40.5) + 0.286 * lobe(530.9, 16.3, 31.1),
+        1.217 * lobe(437.0, 11.8, 36.0) + 0.681 * lobe(459.0, 26.0, 13.8),
+    )
+}
+
+// CIE XYZ to linear sRGB (D65 white point)
+pub fn xyz_to_rgb(xyz: Vec3) -> Color {
+    Color::new(
+        3.2406 * xyz.x() - 1.5372 * xyz.y() - 0.4986 * xyz.z(),
+        -0.9689 * xyz.x() + 1.8758 * xyz.y() + 0.0415 * xyz.z(),
+        0.0557 * xyz.x() - 0.2040 * xyz.y() + 1.0570 * xyz.z(),
+    )
+}
+
+// Color weight of a path traced for a single, uniformly sampled wavelength. Pure spectral colors
+// lie outside of the sRGB gamut, so they are clipped to it, and the weights are scaled so that
+// averaging many samples of white light adds up to white again.
+pub fn wavelength_to_rgb(wavelength: f32) -> Color {
+    static WHITE: OnceLock<Color> = OnceLock::new();
+
+    let white = WHITE.get_or_init(|| {
+        let steps = 4000;
+        let step = (WAVELENGTH_MAX - WAVELENGTH_MIN) / steps as f32;
+
+        (0..steps)
+            .map(|i| clipped_rgb(WAVELENGTH_MIN + (i as f32 + 0.5) * step))
+            .fold(Color::zero(), |sum, rgb| sum + rgb)
+            / steps as f32
+    });
+
+    clipped_rgb(wavelength) / *white
+}
+
+fn clipped_rgb(wavelength: f32) -> Color {
+    let rgb = xyz_to_rgb(wavelength_to_xyz(wavelength));
+
+    Color::new(rgb.x().max(0.), rgb.y().max(0.), rgb.z().max(0.))
+}
+
+// XYZ to linear sRGB, white balanced so a flat spectrum (the same amount of light at every
+// wavelength) comes out as white instead of the slightly pink it is under a D65 white point
+pub fn xyz_to_balanced_rgb(xyz: Vec3) -> Color {
+    static WHITE: OnceLock<Color> = OnceLock::new();
+
+    let white = WHITE.get_or_init(|| {
+        let steps = 4000;
+        let step = (WAVELENGTH_MAX - WAVELENGTH_MIN) / steps as f32;
+
+        xyz_to_rgb(
+            (0..steps)
+                .map(|i| wavelength_to_xyz(WAVELENGTH_MIN + (i as f32 + 0.5) * step))
+                .fold(Vec3::zero(), |sum, xyz| sum + xyz)
+                / steps as f32,
+        )
+    });
+
+    xyz_to_rgb(xyz) / *white
+}
+
+// Smooth spectrum for an RGB color (an albedo or the sky), for rendering RGB scenes spectrally.
+// Blue, green and red each cover a band of wavelengths, with soft edges between the bands. The
+// bands add up to exactly 1 at every wavelength, so white stays white and an albedo between 0 and 1
+// reflects between 0 and 1 of the light at every wavelength.
+pub fn rgb_to_spectrum(color: Color, wavelength: f32) -> f32 {
+    let blue = 1. / (1. + ((wavelength - 490.) / 12.).exp());
+    let red = 1. / (1. + (-(wavelength - 585.) / 12.).exp());
+    let green = (1. - blue - red).max(0.);
+
+    color.x() * red + color.y() * green + color.z() * blue
+}
+
+// Number of wavelengths traced along every path
+pub const HERO_WAVELENGTHS: usize = 4;
+
+// Light along a path at each of the sampled wavelengths
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampledSpectrum(pub [f32; HERO_WAVELENGTHS]);
+
+impl SampledSpectrum {
+    pub fn zero() -> Self {
+        Self([0.; HERO_WAVELENGTHS])
+    }
+}
+
+impl Mul for SampledSpectrum {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] * other.0[i]))
+    }
+}
+
+// Hero wavelength sampling (Wilkie et al., "Hero Wavelength Spectral Sampling", 2014): a path is
+// traced for one randomly picked hero wavelength plus a few more spread evenly across the visible
+// range. All of them follow the hero's path, which costs little more than tracing one wavelength
+// but removes most of the color noise.
+#[derive(Clone, Copy, Debug)]
+pub struct SampledWavelengths {
+    pub wavelengths: [f32; HERO_WAVELENGTHS],
+    secondary_terminated: bool,
+}
+
+impl SampledWavelengths {
+    pub fn sample() -> Self {
+        Self::from_hero(sample_wavelength())
+    }
+
+    // The other wavelengths are rotated away from the hero, wrapping around the visible range
+    pub fn from_hero(hero: f32) -> Self {
+        let range = WAVELENGTH_MAX - WAVELENGTH_MIN;
+
+        Self {
+            wavelengths: std::array::from_fn(|i| {
+                WAVELENGTH_MIN
+                    + (hero - WAVELENGTH_MIN + i as f32 * range / HERO_WAVELENGTHS as f32) % range
+            }),
+            secondary_terminated: false,
+        }
+    }
+
+    pub fn hero(&self) -> f32 {
+        self.wavelengths[0]
+    }
+
+    // A dispersive material sends every wavelength its own way (or weighs them differently, like a
+    // thin film), from then on the path is only valid for the hero wavelength
+    pub fn terminate_secondary(&mut self) {
+        self.secondary_terminated = true;
+    }
+
+    pub fn upsample(&self, color: Color) -> SampledSpectrum {
+        SampledSpectrum(
+            self.wavelengths
+                .map(|wavelength| rgb_to_spectrum(color, wavelength)),
+        )
+    }
+
+    // Estimates the XYZ color of the light arriving along the path
+    pub fn to_xyz(&self, radiance: SampledSpectrum) -> Vec3 {
+        if self.secondary_terminated {
+            return wavelength_to_xyz(self.hero()) * radiance.0[0];
+        }
+
+        self.wavelengths
+            .iter()
+            .zip(radiance.0)
+            .fold(Vec3::zero(), |sum, (wavelength, radiance)| {
+                sum + wavelength_to_xyz(*wavelength) * radiance
+            })
+            / HERO_WAVELENGTHS as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_adds_up_all_wavelengths_to_white() {
+        // Evenly spaced instead of random wavelengths, to keep sampling noise out of the test
+        let samples = 997;
+        let step = (WAVELENGTH_MAX - WAVELENGTH_MIN) / samples as f32;
+        let average = (0..samples)
+            .map(|i| wavelength_to_rgb(WAVELENGTH_MIN + (i as f32 + 0.5) * step))
+            .fold(Color::zero(), |sum, rgb| sum + rgb)
+            / samples as f32;
+
+        assert!(
+            (average - Color::new(1., 1., 1.)).length() < 1e-3,
+            "{average}"
+        );
+        assert!((WAVELENGTH_MIN..WAVELENGTH_MAX).contains(&sample_wavelength()));
+    }
+
+    #[test]
+    fn it_colors_wavelengths() {
+        let blue = wavelength_to_rgb(450.);
+        let green = wavelength_to_rgb(530.);
+        let red = wavelength_to_rgb(650.);
+
+        assert!(blue.z() > blue.x() && blue.z() > blue.y());
+        assert!(green.y() > green.x() && green.y() > green.z());
+        assert!(red.x() > red.y() && red.x() > red.z());
+    }
+
+    #[test]
+    fn it_upsamples_white_to_a_flat_spectrum() {
+        for wavelength in [380., 450., 490., 530., 585., 650., 779.] {
+            let value = rgb_to_spectrum(Color::new(1., 1., 1.), wavelength);
+
+            assert!((value - 1.).abs() < 1e-5, "{wavelength}: {value}");
+        }
+    }
+
+    // Light of `color` seen through hero wavelength samples spread evenly across the spectrum
+    fn render_spectrally(color: Color) -> Color {
+        let samples = 500;
+        let range = (WAVELENGTH_MAX - WAVELENGTH_MIN) / HERO_WAVELENGTHS as f32;
+
+        let xyz = (0..samples)
+            .map(|i| {
+                let wavelengths = SampledWavelengths::from_hero(
+                    WAVELENGTH_MIN + (i as f32 + 0.5) * range / samples as f32,
+                );
+                wavelengths.to_xyz(wavelengths.upsample(color))
+            })
+            .fold(Vec3::zero(), |sum, xyz| sum + xyz)
+            / samples as f32;
+
+        xyz_to_balanced_rgb(xyz)
+    }
+
+    #[test]
+    fn it_round_trips_colors() {
+        let white = render_spectrally(Color::new(1., 1., 1.));
+        assert!((white - Color::new(1., 1., 1.)).length() < 1e-3, "{white}");
+
+        let red = render_spectrally(Color::new(1., 0., 0.));
+        let green = render_spectrally(Color::new(0., 1., 0.));
+        let blue = render_spectrally(Color::new(0., 0., 1.));
+
+        assert!(red.x() > red.y() && red.x() > red.z(), "{red}");
+        assert!(green.y() > green.x() && green.y() > green.z(), "{green}");
+        assert!(blue.z() > blue.x() && blue.z() > blue.y(), "{blue}");
+    }
+
+    #[test]
+    fn it_spreads_wavelengths_across_the_spectrum() {
+        let wavelengths = SampledWavelengths::from_hero(700.);
+
+        assert_eq!(wavelengths.wavelengths, [700., 400., 500., 600.]);
+    }
+
+    #[test]
+    fn it_only_counts_the_hero_once_secondaries_are_terminated() {
+        let mut wavelengths = SampledWavelengths::from_hero(700.);
+        let radiance = SampledSpectrum([1., 0.5, 0.5, 0.5]);
+
+        wavelengths.terminate_secondary();
+
+        assert_eq!(wavelengths.to_xyz(radiance), wavelength_to_xyz(700.));
+    }
+
+    #[test]
+    fn it_peaks_luminance_in_the_green() {
+        // The luminous efficiency (Y) peaks at 555nm
+        assert!((wavelength_to_xyz(555.).y() - 1.).abs() < 0.05);
+        assert!(wavelength_to_xyz(555.).y() > wavelength_to_xyz(450.).y());
+        assert!(wavelength_to_xyz(555.).y() > wavelength_to_xyz(650.).y());
+    }
+}
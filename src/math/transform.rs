@@ -0,0 +1,157 @@
+use super::angles::degrees_to_radians;
+use super::vector::{Point, Vec3};
+use glam::{Affine3A, Mat3A};
+
+// Affine transformation (any combination of translation, rotation and scale) from object space to
+// world space. The inverse is calculated once up front because rays are transformed from world
+// space into object space far more often than a transform is built.
+#[derive(Clone, Copy, Debug)]
+pub struct Transform {
+    matrix: Affine3A,
+    inverse: Affine3A,
+    normal_matrix: Mat3A, // Inverse transpose of the linear part, transforms normals to world space
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Self::from_affine(Affine3A::IDENTITY)
+    }
+
+    fn from_affine(matrix: Affine3A) -> Self {
+        let inverse = matrix.inverse();
+
+        Self {
+            matrix,
+            inverse,
+            normal_matrix: inverse.matrix3.transpose(),
+        }
+    }
+
+    // Applies `other` after the transformations already in this one
+    fn then(self, other: Affine3A) -> Self {
+        Self::from_affine(other * self.matrix)
+    }
+
+    // The methods below each add a step to the transformation, steps are applied in call order:
+    // `Transform::identity().scale(..).rotate_y(..).translate(..)` scales first and translates last.
+    pub fn translate(self, offset: Vec3) -> Self {
+        self.then(Affine3A::from_translation(offset.into()))
+    }
+
+    // Non-uniform scale along the object's x, y and z axes
+    pub fn scale(self, factors: Vec3) -> Self {
+        self.then(Affine3A::from_scale(factors.into()))
+    }
+
+    pub fn rotate_x(self, degrees: f32) -> Self {
+        self.then(Affine3A::from_rotation_x(degrees_to_radians(degrees)))
+    }
+
+    pub fn rotate_y(self, degrees: f32) -> Self {
+        self.then(Affine3A::from_rotation_y(degrees_to_radians(degrees)))
+    }
+
+    pub fn rotate_z(self, degrees: f32) -> Self {
+        self.then(Affine3A::from_rotation_z(degrees_to_radians(degrees)))
+    }
+
+    // Rotation around an arbitrary axis through the origin
+    pub fn rotate(self, axis: Vec3, degrees: f32) -> Self {
+        self.then(Affine3A::from_axis_angle(
+            axis.unit().into(),
+            degrees_to_radians(degrees),
+        ))
+    }
+
+    // Object space -> world space
+    pub fn point(&self, p: Point) -> Point {
+        self.matrix.transform_point3a(p.into()).into()
+    }
+
+    pub fn vector(&self, v: Vec3) -> Vec3 {
+        self.matrix.transform_vector3a(v.into()).into()
+    }
+
+    // Normals need the inverse transpose to stay perpendicular to a non-uniformly scaled surface.
+    // The result is not unit length.
+    pub fn normal(&self, n: Vec3) -> Vec3 {
+        (self.normal_matrix * glam::Vec3A::from(n)).into()
+    }
+
+    // World space -> object space
+    pub fn inverse_point(&self, p: Point) -> Point {
+        self.inverse.transform_point3a(p.into()).into()
+    }
+
+    pub fn inverse_vector(&self, v: Vec3) -> Vec3 {
+        self.inverse.transform_vector3a(v.into()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Vec3, expected: Vec3) {
+        assert!(
+            (actual - expected).length() < 1e-5,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn it_translates_points_but_not_vectors() {
+        let transform = Transform::identity().translate(Vec3::new(1., 2., 3.));
+
+        assert_near(
+            transform.point(Point::new(1., 1., 1.)),
+            Point::new(2., 3., 4.),
+        );
+        assert_near(
+            transform.vector(Vec3::new(1., 1., 1.)),
+            Vec3::new(1., 1., 1.),
+        );
+    }
+
+    #[test]
+    fn it_applies_steps_in_call_order() {
+        let transform = Transform::identity()
+            .scale(Vec3::new(2., 2., 2.))
+            .rotate_z(90.)
+            .translate(Vec3::new(0., 0., -5.));
+
+        // (1, 0, 0) -> scaled (2, 0, 0) -> rotated (0, 2, 0) -> translated (0, 2, -5)
+        assert_near(
+            transform.point(Point::new(1., 0., 0.)),
+            Point::new(0., 2., -5.),
+        );
+    }
+
+    #[test]
+    fn it_inverts() {
+        let transform = Transform::identity()
+            .scale(Vec3::new(1., 3., 0.5))
+            .rotate(Vec3::new(1., 1., 0.), 30.)
+            .translate(Vec3::new(4., -2., 1.));
+
+        let p = Point::new(0.3, -1.2, 2.5);
+        let v = Vec3::new(-0.7, 0.1, 0.4);
+
+        assert_near(transform.inverse_point(transform.point(p)), p);
+        assert_near(transform.inverse_vector(transform.vector(v)), v);
+    }
+
+    #[test]
+    fn it_keeps_normals_perpendicular_under_non_uniform_scale() {
+        let transform = Transform::identity().scale(Vec3::new(4., 1., 1.));
+
+        // Surface of the plane x + y = 0, spanned by this tangent and with this normal
+        let tangent = Vec3::new(1., -1., 0.);
+        let normal = Vec3::new(1., 1., 0.);
+
+        let world_tangent = transform.vector(tangent);
+        let world_normal = transform.normal(normal);
+
+        assert!(world_tangent.dot(world_normal).abs() < 1e-5);
+    }
+}
@@ -62,6 +62,17 @@ impl Vec3 {
         }
     }
 
+    // Random vector within the unit disk in the xy plane
+    pub fn random_in_unit_disk() -> Self {
+        loop {
+            let p = Self::new(random_f32(-1., 1.), random_f32(-1., 1.), 0.);
+
+            if p.length_squared() < 1. {
+                return p;
+            }
+        }
+    }
+
     // Generate a random Vector that is located on the same hemisphere as the specified normal vector
     pub fn random_unit_on_hemisphere(normal: &Vec3) -> Vec3 {
         let on_unit_sphere = Self::random_unit();
@@ -159,6 +170,33 @@ impl Vec3 {
     }
 }
 
+// Conversions to and from the underlying library type, for math built directly on glam
+impl From<glam::Vec3> for Vec3 {
+    fn from(inner: glam::Vec3) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Vec3> for glam::Vec3 {
+    fn from(v: Vec3) -> Self {
+        v.inner
+    }
+}
+
+impl From<glam::Vec3A> for Vec3 {
+    fn from(inner: glam::Vec3A) -> Self {
+        Self {
+            inner: inner.into(),
+        }
+    }
+}
+
+impl From<Vec3> for glam::Vec3A {
+    fn from(v: Vec3) -> Self {
+        v.inner.into()
+    }
+}
+
 impl Display for Vec3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Vec3({:.2}, {:.2}, {:.2})", self.x(), self.y(), self.z())
@@ -41,7 +41,7 @@ impl Image {
 }
 
 impl Output for Image {
-    fn init(&mut self) -> () {
+    fn init(&mut self) {
         self.buffer = Some(RgbImage::new(self.w, self.h));
     }
 
@@ -65,7 +65,7 @@ impl Output for Image {
         }
     }
 
-    fn save(&self, save_path: &String) -> () {
+    fn save(&self, save_path: &str) {
         let path = Path::new(save_path);
 
         match self.buffer {
@@ -3,9 +3,9 @@ use std::str::FromStr;
 
 // Represents a generic output of a rendering algorithm
 pub trait Output {
-    fn init(&mut self) -> ();
+    fn init(&mut self);
     fn put_pixel(&mut self, x: u32, y: u32, c: &Color);
-    fn save(&self, save_path: &String) -> ();
+    fn save(&self, save_path: &str);
 }
 
 #[derive(Debug)]
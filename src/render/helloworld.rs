@@ -7,6 +7,12 @@ use crate::scene::viewport::Viewport;
 // Renders a hardcoded color gradient to output
 pub struct HelloWorld {}
 
+impl Default for HelloWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HelloWorld {
     pub fn new() -> Self {
         Self {}
@@ -57,14 +63,6 @@ impl Renderer for HelloWorld {
         Ok(output)
     }
 
-    fn debug_ray(
-        &mut self,
-        _x: u32,
-        _y: u32,
-        _scene: &Scene,
-        _camera_name: String,
-        _image_w: u32,
-    ) -> () {
-        ()
+    fn debug_ray(&mut self, _x: u32, _y: u32, _scene: &Scene, _camera_name: String, _image_w: u32) {
     }
 }
@@ -1,9 +1,10 @@
 use crate::geometry::ray::Ray;
 use crate::math::chance::random_f32;
+use crate::math::spectrum::{SampledSpectrum, SampledWavelengths, xyz_to_balanced_rgb};
 use crate::math::vector::{Color, Vec3};
 use crate::output::output::{Output, OutputType};
 use crate::render::renderer::{Renderer, get_output};
-use crate::scene::camera::Camera;
+use crate::scene::camera::{Camera, Projection};
 use crate::scene::scene::Scene;
 use crate::scene::viewport::Viewport;
 use std::io;
@@ -14,6 +15,9 @@ pub struct PathTracer {
     samples_per_pixel: u32,
     pixel_samples_scale: f32,
     max_depth: u32, // Maximum number of ray bounces into scene
+    // Carry sampled wavelengths along every path instead of RGB colors. Slower, but dispersion is
+    // traced without the color noise of picking a single wavelength per path.
+    spectral: bool,
 }
 
 impl PathTracer {
@@ -22,10 +26,19 @@ impl PathTracer {
             samples_per_pixel,
             pixel_samples_scale: 1.0 / samples_per_pixel as f32,
             max_depth,
+            spectral: false,
         }
     }
 
-    fn get_ray(&self, camera: &Camera, viewport: &Viewport, x: u32, y: u32) -> Ray {
+    pub fn new_spectral(samples_per_pixel: u32, max_depth: u32) -> Self {
+        Self {
+            spectral: true,
+            ..Self::new(samples_per_pixel, max_depth)
+        }
+    }
+
+    // Ray for a random point within pixel (x, y), None where the camera sees nothing
+    fn get_ray(&self, camera: &Camera, viewport: &Viewport, x: u32, y: u32) -> Option<Ray> {
         let offset = self.sample_square();
 
         // Get the center location of the pixel on the viewport plane to calculate its color
@@ -33,8 +46,59 @@ impl PathTracer {
             + ((x as f32 + offset.x()) * viewport.delta_u)
             + ((y as f32 + offset.y()) * viewport.delta_v);
 
-        // Construct a ray originating at the camera center pointed towards the pixel we are rendering
-        Ray::from_to(camera.look_from, pixel)
+        // A real lens bends the rays itself, from the film to the world in front of it
+        let ray = if let Some(lens) = &camera.lens {
+            let s = (x as f32 + 0.5 + offset.x()) / viewport.image_w as f32;
+            let t = (y as f32 + 0.5 + offset.y()) / viewport.image_h as f32;
+
+            let (u, v, w) = camera.basis();
+            let world = |p: Vec3| p.x() * u + p.y() * v + p.z() * w;
+
+            lens.ray(s, t, camera.aspect_ratio, Vec3::random_in_unit_disk())
+                .map(|ray| Ray::new(camera.look_from + world(ray.origin), world(ray.direction)))
+        } else {
+            match camera.projection {
+                // Construct a ray originating at the camera center pointed towards the pixel we
+                // are rendering
+                Projection::Perspective => {
+                    // A physical camera lets light in through its whole aperture, starting the ray
+                    // at a random point on it. Only things at the viewport (the focus distance) end
+                    // up sharp.
+                    let origin = match &camera.exposure {
+                        Some(exposure) => {
+                            let (u, v, _) = camera.basis();
+                            let lens =
+                                camera.aperture.sample() * (exposure.aperture_diameter() / 2.);
+
+                            camera.look_from + lens.x() * u + lens.y() * v
+                        }
+                        None => camera.look_from,
+                    };
+
+                    Some(Ray::from_to(origin, pixel))
+                }
+                // Parallel rays, each starting at its pixel on the viewport
+                Projection::Orthographic { .. } => {
+                    Some(Ray::new(pixel, camera.look_at - camera.look_from))
+                }
+                // Panoramic rays start at the camera center, in a direction given by where the
+                // pixel is in the image
+                Projection::Equirectangular | Projection::Fisheye { .. } | Projection::CubeMap => {
+                    let s = (x as f32 + 0.5 + offset.x()) / viewport.image_w as f32;
+                    let t = (y as f32 + 0.5 + offset.y()) / viewport.image_h as f32;
+
+                    camera
+                        .panoramic_direction(s, t)
+                        .map(|direction| Ray::new(camera.look_from, direction))
+                }
+            }
+        }?;
+
+        // Each ray samples a moment while the shutter is open
+        match &camera.exposure {
+            Some(exposure) => Some(ray.with_time(random_f32(0., exposure.shutter_speed))),
+            None => Some(ray),
+        }
     }
 
     fn sample_square(&self) -> Vec3 {
@@ -44,16 +108,36 @@ impl PathTracer {
     fn calculate_pixel(&self, scene: &Scene, ray: &Ray, depth: u32) -> Color {
         ray_debug!("Depth {} - calculating pixel", depth);
 
-        if depth <= 0 {
+        if depth == 0 {
             ray_debug!("Depth {} - max depth, color black", depth);
 
             // Max depth reached, stop tracing
             return Color::zero(); // Contribute no more light to the pixel
         }
 
-        match scene.trace(ray) {
-            (Some(obj), Some(hit)) => match obj.material().scatter(&ray, &hit) {
+        let (obj, hit) = scene.trace(ray);
+
+        // Participating medium filling the scene: the ray may scatter before it reaches the surface
+        if let Some(fog) = &scene.fog {
+            let distance = hit.as_ref().map(|hit| hit.t * ray.direction.length());
+
+            if let Some((scatter, attenuation)) = fog.scatter(ray, distance) {
+                ray_debug!("Depth {} - ray scattered in fog to {}", depth, scatter);
+
+                let scatter = scatter.with_wavelength(ray.wavelength).with_time(ray.time);
+
+                return attenuation * self.calculate_pixel(scene, &scatter, depth - 1);
+            }
+        }
+
+        match (obj, hit) {
+            (Some(obj), Some(hit)) => match obj.material().scatter(ray, &hit) {
                 Some((scatter, attenuation)) => {
+                    // Once a path is traced for a single wavelength it keeps it
+                    let scatter = scatter
+                        .with_wavelength(scatter.wavelength.or(ray.wavelength))
+                        .with_time(ray.time);
+
                     ray_debug!(
                         "Depth {} - ray scattered to {} at an angle of {}°, current attenuation {}",
                         depth,
@@ -73,18 +157,155 @@ impl PathTracer {
             _ => {
                 ray_debug!("NO HIT - USING BACKGROUND");
 
-                // Nothing was hit, fall back to background gradient
-                let unit_direction = ray.direction.unit();
-                let a = 0.5 * unit_direction.y() + 1.0;
+                Self::background(ray)
+            }
+        }
+    }
+
+    // Same as calculate_pixel, but light is carried as a spectrum sampled at the path's wavelengths.
+    // Materials still work in RGB, their colors are turned into spectra along the way.
+    fn calculate_spectrum(
+        &self,
+        scene: &Scene,
+        ray: &Ray,
+        depth: u32,
+        wavelengths: &mut SampledWavelengths,
+    ) -> SampledSpectrum {
+        if depth == 0 {
+            ray_debug!("Depth {} - max depth, no light", depth);
+
+            return SampledSpectrum::zero();
+        }
+
+        let (obj, hit) = scene.trace(ray);
+
+        if let Some(fog) = &scene.fog {
+            let distance = hit.as_ref().map(|hit| hit.t * ray.direction.length());
 
-                // Blend (or "lerp", linear interpolation) of white and blue based on the ray's Y coordinate
-                (1.0 - a) * Color::new(1., 1., 1.) + a * Color::new(0.5, 0.7, 1.)
+            if let Some((scatter, attenuation)) = fog.scatter(ray, distance) {
+                let scatter = scatter.with_wavelength(ray.wavelength).with_time(ray.time);
+
+                return wavelengths.upsample(attenuation)
+                    * self.calculate_spectrum(scene, &scatter, depth - 1, wavelengths);
+            }
+        }
+
+        match (obj, hit) {
+            (Some(obj), Some(hit)) => {
+                // The ray carries the hero wavelength, the only one dispersive materials work with
+                if obj.material().is_dispersive() {
+                    wavelengths.terminate_secondary();
+                }
+
+                match obj.material().scatter(ray, &hit) {
+                    Some((scatter, attenuation)) => {
+                        let scatter = scatter
+                            .with_wavelength(scatter.wavelength.or(ray.wavelength))
+                            .with_time(ray.time);
+
+                        ray_debug!(
+                            "Depth {} - ray scattered to {}, current attenuation {}",
+                            depth,
+                            scatter,
+                            attenuation
+                        );
+
+                        wavelengths.upsample(attenuation)
+                            * self.calculate_spectrum(scene, &scatter, depth - 1, wavelengths)
+                    }
+                    None => SampledSpectrum::zero(),
+                }
             }
+            _ => wavelengths.upsample(Self::background(ray)),
+        }
+    }
+
+    // Nothing was hit, fall back to background gradient
+    fn background(ray: &Ray) -> Color {
+        let unit_direction = ray.direction.unit();
+        let a = 0.5 * unit_direction.y() + 1.0;
+
+        // Blend (or "lerp", linear interpolation) of white and blue based on the ray's Y coordinate
+        (1.0 - a) * Color::new(1., 1., 1.) + a * Color::new(0.5, 0.7, 1.)
+    }
+
+    // Color of a single sample along `ray`: RGB, or XYZ in spectral mode
+    fn sample(&self, scene: &Scene, ray: Ray) -> Color {
+        if !self.spectral {
+            return self.calculate_pixel(scene, &ray, self.max_depth);
+        }
+
+        let mut wavelengths = SampledWavelengths::sample();
+        let ray = ray.with_wavelength(Some(wavelengths.hero()));
+        let radiance = self.calculate_spectrum(scene, &ray, self.max_depth, &mut wavelengths);
+
+        wavelengths.to_xyz(radiance)
+    }
+
+    // Renders what the camera sees into the output, starting at the given pixel offset
+    fn render_view(
+        &self,
+        scene: &Scene,
+        camera: &Camera,
+        viewport: &Viewport,
+        output: &mut dyn Output,
+        offset_x: u32,
+        offset_y: u32,
+    ) {
+        let total_pixels = (viewport.image_w * viewport.image_h) as usize;
+        let mut count = 0;
+
+        for x in 0..viewport.image_w {
+            for y in 0..viewport.image_h {
+                count += 1;
+                Self::print_progress(total_pixels, count);
+
+                let mut color = Color::zero();
+
+                // We sample a number of rays for the same pixel and use the average color. This
+                // implements antialiasing.
+                for _ in 0..self.samples_per_pixel {
+                    // No ray, no light: black
+                    let Some(ray) = self.get_ray(camera, viewport, x, y) else {
+                        continue;
+                    };
+
+                    // Simple line where the bulk of the complexity lies: find out which color the
+                    // pixel should have based on the Scene geometry, lights, materials, ...
+                    color = color + self.sample(scene, ray);
+                }
+
+                // We have added colors for all samples, now we calculate the average
+                color = color * self.pixel_samples_scale;
+
+                if self.spectral {
+                    color = xyz_to_balanced_rgb(color);
+                }
+
+                if let Some(exposure) = &camera.exposure {
+                    color = color * exposure.multiplier();
+                }
+
+                if let Some(lens) = &camera.lens {
+                    color = color * lens.brightness();
+                }
+
+                output.put_pixel(offset_x + x, offset_y + y, &color);
+            }
+        }
+    }
+
+    // The views rendered for a camera and their width: a stereo camera renders a view for each eye
+    // into one output image
+    fn views(camera: &Camera, image_w: u32) -> (Vec<Camera>, u32) {
+        match &camera.stereo {
+            Some(stereo) => (stereo.eyes(camera).to_vec(), stereo.view_width(image_w)),
+            None => (vec![camera.clone()], image_w),
         }
     }
 
     fn print_progress(total_pixels: usize, count: usize) {
-        if count % 1000 == 0 || count == total_pixels {
+        if count.is_multiple_of(1000) || count == total_pixels {
             let percent = count as f32 / total_pixels as f32 * 100.0;
             print!("\rRendering: {:>5.1}%", percent);
             io::stdout().flush().unwrap();
@@ -104,8 +325,18 @@ impl Renderer for PathTracer {
             .get_camera(&camera_name)
             .ok_or(format!("Camera {camera_name} not found"))?;
 
-        let viewport = Viewport::from(camera, image_w);
-        let image_h = viewport.image_h; // Height is determined from specified width and camera aspect ratio
+        let (views, view_w) = Self::views(camera, image_w);
+
+        let viewports: Vec<Viewport> = views
+            .iter()
+            .map(|view| Viewport::from(view, view_w))
+            .collect();
+        let view_h = viewports[0].image_h; // Height is determined from specified width and camera aspect ratio
+
+        let (image_w, image_h) = match &camera.stereo {
+            Some(stereo) => stereo.output_size(view_w, view_h),
+            None => (view_w, view_h),
+        };
 
         let mut output = get_output(image_w, image_h, &output_type);
 
@@ -114,35 +345,17 @@ impl Renderer for PathTracer {
             scene.name, output_type,
         );
 
-        println!("Viewport: {viewport:#?}");
-
         output.init();
 
-        let total_pixels = (image_w * image_h) as usize;
-        let mut count = 0;
+        for (i, (view, viewport)) in views.iter().zip(viewports.iter()).enumerate() {
+            println!("Viewport: {viewport:#?}");
 
-        for x in 0..image_w {
-            for y in 0..image_h {
-                count += 1;
-                Self::print_progress(total_pixels, count);
-
-                let mut color = Color::zero();
-
-                // We sample a number of rays for the same pixel and use the average color. This
-                // implements antialiasing.
-                for _ in 0..self.samples_per_pixel {
-                    let ray = self.get_ray(&camera, &viewport, x, y);
+            let (offset_x, offset_y) = match &camera.stereo {
+                Some(stereo) => stereo.view_offset(i, view_w, view_h),
+                None => (0, 0),
+            };
 
-                    // Simple line where the bulk of the complexity lies: find out which color the
-                    // pixel should have based on the Scene geometry, lights, materials, ...
-                    color = color + self.calculate_pixel(scene, &ray, self.max_depth);
-                }
-
-                // We have added colors for all samples, now we calculate the average
-                color = color * self.pixel_samples_scale;
-
-                output.put_pixel(x, y, &color);
-            }
+            self.render_view(scene, view, viewport, output.as_mut(), offset_x, offset_y);
         }
 
         println!(" -> Done!");
@@ -150,33 +363,48 @@ impl Renderer for PathTracer {
         Ok(output)
     }
 
-    fn debug_ray(
-        &mut self,
-        x: u32,
-        y: u32,
-        scene: &Scene,
-        camera_name: String,
-        image_w: u32,
-    ) -> () {
-        let camera = scene.get_camera(&camera_name).unwrap();
-        let viewport = Viewport::from(camera, image_w);
+    fn debug_ray(&mut self, x: u32, y: u32, scene: &Scene, camera_name: String, image_w: u32) {
+        let Some(camera) = scene.get_camera(&camera_name) else {
+            println!("Camera {camera_name} not found");
+            return;
+        };
+        // Find the view showing the pixel, like render lays them out
+        let (views, view_w) = Self::views(camera, image_w);
+        let view_h = Viewport::from(&views[0], view_w).image_h;
+
+        let (eye, view_x, view_y) = match &camera.stereo {
+            Some(stereo) => stereo.view_at(x, y, view_w, view_h),
+            None => (0, x, y),
+        };
+
+        let view = &views[eye.min(views.len() - 1)];
+        let viewport = Viewport::from(view, view_w);
 
         ray_debug!(
-            "PathTracer debugging of ray at {},{} in scene {} - {} x {} image",
+            "PathTracer debugging of ray at {},{} in scene {} - view {} at {},{} of {} x {}",
             x,
             y,
             scene.name,
-            image_w,
-            viewport.image_h,
+            view.name,
+            view_x,
+            view_y,
+            view_w,
+            view_h,
         );
 
-        let ray = self.get_ray(&camera, &viewport, x, y);
+        let Some(ray) = self.get_ray(view, &viewport, view_x, view_y) else {
+            println!("No ray for pixel {x},{y}, the camera sees nothing there");
+            return;
+        };
 
         ray_debug!("Tracing ray {}", ray);
 
-        println!(
-            "Pixel color: {:?}",
-            self.calculate_pixel(scene, &ray, self.max_depth)
-        );
+        let color = self.sample(scene, ray);
+
+        if self.spectral {
+            println!("Pixel color: {:?}", xyz_to_balanced_rgb(color));
+        } else {
+            println!("Pixel color: {:?}", color);
+        }
     }
 }
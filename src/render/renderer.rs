@@ -12,8 +12,7 @@ pub trait Renderer {
     ) -> Result<Box<dyn Output>, String>;
 
     // Calculate a single ray for the specified pixel to see how it travels through the scene
-    fn debug_ray(&mut self, x: u32, y: u32, scene: &Scene, camera_name: String, image_w: u32)
-    -> ();
+    fn debug_ray(&mut self, x: u32, y: u32, scene: &Scene, camera_name: String, image_w: u32);
 }
 
 pub fn get_output(w: u32, h: u32, output_type: &OutputType) -> Box<dyn Output> {
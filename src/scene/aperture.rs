@@ -0,0 +1,193 @@
+use crate::math::angles::degrees_to_radians;
+use crate::math::chance::random_f32;
+use crate::math::constants::PI;
+use crate::math::vector::Vec3;
+
+// Shape of the opening light enters a camera through. Out of focus, every bright point in the scene
+// is smeared out into this shape (bokeh).
+#[derive(Clone, Debug, Default)]
+pub enum Aperture {
+    // Ideal round opening
+    #[default]
+    Circle,
+    // Opening formed by straight diaphragm blades, as in most real lenses when stopped down.
+    // Rotation in degrees, at zero a corner points up.
+    Polygon {
+        blades: u32,
+        rotation: f32,
+    },
+    // Any shape, drawn in an image
+    Mask(ApertureMask),
+}
+
+impl Aperture {
+    // Random point on the aperture, within the unit disk
+    pub fn sample(&self) -> Vec3 {
+        match self {
+            Aperture::Circle => Vec3::random_in_unit_disk(),
+            Aperture::Polygon { blades, rotation } => Self::sample_polygon(*blades, *rotation),
+            Aperture::Mask(mask) => mask.sample(),
+        }
+    }
+
+    // The polygon is made of equal triangles around the center: pick one, then a point in it
+    fn sample_polygon(blades: u32, rotation: f32) -> Vec3 {
+        let blades = blades.max(3);
+        let triangle = ((random_f32(0., 1.) * blades as f32) as u32).min(blades - 1);
+
+        let corner = |i: u32| {
+            let angle = degrees_to_radians(rotation) + 2. * PI * i as f32 / blades as f32;
+
+            Vec3::new(-angle.sin(), angle.cos(), 0.)
+        };
+
+        // Uniform over the triangle between the center and two neighbouring corners
+        let (a, b) = (random_f32(0., 1.).sqrt(), random_f32(0., 1.));
+
+        a * ((1. - b) * corner(triangle) + b * corner(triangle + 1))
+    }
+}
+
+// Aperture shape from an image: light passes where the image is bright, partly where it is grey.
+// The image is fit into the unit disk, so its corners should be dark.
+#[derive(Clone, Debug)]
+pub struct ApertureMask {
+    width: usize,
+    height: usize,
+    // Running total of the pixel brightnesses row by row, to pick pixels by how bright they are
+    cumulative: Vec<f32>,
+}
+
+impl ApertureMask {
+    // Brightness of every pixel row by row from the top, from 0 (blocked) to 1 (open)
+    pub fn new(width: usize, height: usize, pixels: Vec<f32>) -> Result<Self, String> {
+        if width == 0 || height == 0 || pixels.len() != width * height {
+            return Err(format!(
+                "Aperture mask of {width}x{height} needs {} pixels, found {}",
+                width * height,
+                pixels.len()
+            ));
+        }
+
+        let cumulative = pixels
+            .iter()
+            .scan(0., |total, pixel| {
+                *total += pixel.clamp(0., 1.);
+                Some(*total)
+            })
+            .collect::<Vec<f32>>();
+
+        if cumulative[cumulative.len() - 1] <= 0. {
+            return Err(String::from("Aperture mask lets no light through"));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cumulative,
+        })
+    }
+
+    // Draws the mask with a function of the position on the aperture, x right and y up, both from
+    // -1 to 1
+    pub fn from_fn(size: usize, brightness: impl Fn(f32, f32) -> f32) -> Result<Self, String> {
+        let to_aperture = |i: usize| (i as f32 + 0.5) / size as f32 * 2. - 1.;
+
+        let pixels = (0..size * size)
+            .map(|i| brightness(to_aperture(i % size), -to_aperture(i / size)))
+            .collect();
+
+        Self::new(size, size, pixels)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let image = image::open(path)
+            .map_err(|e| format!("Could not load aperture {path}: {e}"))?
+            .to_luma8();
+
+        let pixels = image
+            .pixels()
+            .map(|pixel| pixel.0[0] as f32 / 255.)
+            .collect();
+
+        Self::new(image.width() as usize, image.height() as usize, pixels)
+    }
+
+    // Random point on the open part of the mask, brighter pixels are picked more often
+    pub fn sample(&self) -> Vec3 {
+        let total = self.cumulative[self.cumulative.len() - 1];
+        let pick = random_f32(0., total);
+
+        let pixel = self
+            .cumulative
+            .partition_point(|&sum| sum <= pick)
+            .min(self.cumulative.len() - 1);
+
+        // Somewhere inside the pixel, the longest side of the image spans the disk
+        let x = (pixel % self.width) as f32 + random_f32(0., 1.);
+        let y = (pixel / self.width) as f32 + random_f32(0., 1.);
+        let scale = 2. / self.width.max(self.height) as f32;
+
+        Vec3::new(
+            (x - self.width as f32 / 2.) * scale,
+            (self.height as f32 / 2. - y) * scale,
+            0.,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_samples_inside_a_polygon() {
+        let aperture = Aperture::Polygon {
+            blades: 4,
+            rotation: 45.,
+        };
+
+        // A square with its sides along the axes, corners on the unit circle
+        let half_side = 1. / 2f32.sqrt();
+
+        for _ in 0..1000 {
+            let p = aperture.sample();
+
+            assert!(p.x().abs() <= half_side + 1e-5 && p.y().abs() <= half_side + 1e-5);
+        }
+    }
+
+    #[test]
+    fn it_samples_where_the_mask_is_open() {
+        // Only the right half of the aperture lets light through
+        let mask = ApertureMask::from_fn(8, |x, _| if x > 0. { 1. } else { 0. }).unwrap();
+        let aperture = Aperture::Mask(mask);
+
+        for _ in 0..1000 {
+            let p = aperture.sample();
+
+            assert!(p.x() >= 0. && p.x() <= 1. && p.y().abs() <= 1.);
+        }
+    }
+
+    #[test]
+    fn it_loads_a_mask_image() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/apertures/star.png");
+        let star = Aperture::Mask(ApertureMask::load(path).unwrap());
+
+        // The star's arms don't reach the corners of the image
+        for _ in 0..1000 {
+            let p = star.sample();
+
+            assert!(p.length() <= 1.05, "{p}");
+        }
+
+        assert!(ApertureMask::load("apertures/does_not_exist.png").is_err());
+    }
+
+    #[test]
+    fn it_rejects_masks_that_let_no_light_through() {
+        assert!(ApertureMask::from_fn(4, |_, _| 0.).is_err());
+        assert!(ApertureMask::new(2, 2, vec![1.; 3]).is_err());
+    }
+}
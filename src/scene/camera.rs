@@ -1,18 +1,72 @@
+use crate::math::angles::degrees_to_radians;
+use crate::math::constants::PI;
+use crate::math::onb::Onb;
 use crate::math::vector::{Point, Vec3};
+use crate::scene::aperture::Aperture;
+use crate::scene::exposure::Exposure;
+use crate::scene::lens::LensSystem;
+use crate::scene::stereo::Stereo;
+
+// How the scene is projected onto the viewport
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    // Rays fan out from the camera through the viewport, far away things look smaller. This is how
+    // eyes and real cameras see.
+    Perspective,
+    // Parallel rays, things keep their size however far away they are, as in technical drawings
+    // and isometric views. The view width is in world units, the field of view is not used.
+    Orthographic { view_width: f32 },
+    // Panoramic projections capture more than a viewport could, rays are generated per pixel
+    // straight from the position in the image instead.
+    //
+    // All directions around the camera: longitude across the image (the camera looks at the
+    // middle), latitude from top to bottom. Rendered at a 2:1 aspect ratio.
+    Equirectangular,
+    // Equidistant fisheye: the angle with the view direction grows with the distance from the
+    // middle of the image, up to half the field of view (in degrees, up to 360) at the edge of
+    // the circle. Rendered square, the corners outside the circle stay black.
+    Fisheye { field_of_view: f32 },
+    // Six square faces, like the inside of a cube around the camera, at a 3:2 aspect ratio. The top
+    // row shows right, left and up, the bottom row down, front and back. Each face is a 90 degree
+    // perspective view.
+    CubeMap,
+}
 
 // Defines from where a scene is observed, what the camera sees is rendered to the output image
+#[derive(Clone, Debug)]
 pub struct Camera {
     pub name: String,
 
     pub look_from: Point, // Position of the camera in the world
     pub look_at: Point,   // Point towards which the camera is aimed
-    pub up: Vec3, // Points towards the top of the camera (manipulating this would rotate the camera)
+    pub up: Vec3, // Points towards the top of the camera, the world's up unless the camera is tilted
+    pub roll: f32, // Degrees the camera is rotated around its view direction, positive tilts it to the right
 
     pub field_of_view: f32, // Vertical view angle in degrees - angle at look_from between top and bottom edge of viewport
 
     // Camera field of vision
     pub focal_length: f32, // Determines distance of the viewport from the camera
     pub aspect_ratio: f32, // Camera aspect ratio, determines height of the camera's view
+
+    pub projection: Projection,
+
+    // Moves the viewport sideways (to the right) in world units, without turning the camera
+    pub viewport_shift: f32,
+
+    // Renders the view from two eyes instead, for VR
+    pub stereo: Option<Stereo>,
+
+    // Physical camera settings: brightness, depth of field and motion blur. Without them the
+    // camera is an ideal pinhole with an instant shutter.
+    pub exposure: Option<Exposure>,
+    // Shape of the opening of the exposure's lens, out of focus highlights take this shape
+    pub aperture: Aperture,
+
+    // Traces rays through a real lens instead, which then sets the field of view, depth of field
+    // and vignetting. The exposure still sets the brightness and the shutter. Its f-stop is not
+    // derived from the lens, it only sets the brightness and should match the stop of the lens
+    // prescription. The depth of field comes from the prescription's stop, `aperture` is ignored.
+    pub lens: Option<LensSystem>,
 }
 
 impl Camera {
@@ -27,10 +81,341 @@ impl Camera {
             name,
             look_from,
             look_at,
-            up: Vec3::new(0., 1., 0.), // Fixed for now
+            up: Vec3::new(0., 1., 0.),
+            roll: 0.,
             field_of_view,
             focal_length: (look_from - look_at).length(),
             aspect_ratio,
+            projection: Projection::Perspective,
+            viewport_shift: 0.,
+            stereo: None,
+            exposure: None,
+            aperture: Aperture::Circle,
+            lens: None,
+        }
+    }
+
+    // Camera at `position` turned by angles in degrees, with the y axis up. At zero the camera looks
+    // along -z. Yaw turns it to the right around the y axis, pitch tilts it up and roll rotates it
+    // around the view direction.
+    pub fn from_angles(
+        name: String,
+        position: Point,
+        yaw: f32,
+        pitch: f32,
+        roll: f32,
+        field_of_view: f32,
+        aspect_ratio: f32,
+    ) -> Self {
+        let (yaw, pitch) = (degrees_to_radians(yaw), degrees_to_radians(pitch));
+
+        let direction = Vec3::new(
+            yaw.sin() * pitch.cos(),
+            pitch.sin(),
+            -yaw.cos() * pitch.cos(),
+        );
+
+        // Tilted along with the pitch, so looking straight up or down still knows where the top
+        // of the image is
+        let up = Vec3::new(
+            -yaw.sin() * pitch.sin(),
+            pitch.cos(),
+            yaw.cos() * pitch.sin(),
+        );
+
+        Self::new(
+            name,
+            position,
+            position + direction,
+            field_of_view,
+            aspect_ratio,
+        )
+        .with_up(up)
+        .with_roll(roll)
+    }
+
+    pub fn with_up(mut self, up: Vec3) -> Self {
+        self.up = up.unit();
+        self
+    }
+
+    pub fn with_roll(mut self, roll: f32) -> Self {
+        self.roll = roll;
+        self
+    }
+
+    // Focuses on look_at, so the distance to it is the focus distance
+    pub fn with_exposure(mut self, exposure: Exposure) -> Self {
+        self.exposure = Some(exposure);
+        self
+    }
+
+    pub fn with_aperture(mut self, aperture: Aperture) -> Self {
+        self.aperture = aperture;
+        self
+    }
+
+    // Focuses the lens on look_at. The film sits at look_from with the lens in front of it. The
+    // exposure's f-stop and the aperture no longer shape the depth of field, see `lens`.
+    pub fn with_lens(mut self, lens: LensSystem) -> Self {
+        self.lens = Some(lens.focused(self.focal_length));
+        self
+    }
+
+    pub fn with_stereo(mut self, stereo: Stereo) -> Self {
+        self.stereo = Some(stereo);
+        self
+    }
+
+    pub fn with_orthographic(mut self, view_width: f32) -> Self {
+        self.projection = Projection::Orthographic { view_width };
+        self
+    }
+
+    pub fn with_equirectangular(mut self) -> Self {
+        self.projection = Projection::Equirectangular;
+        self.aspect_ratio = 2.;
+        self
+    }
+
+    pub fn with_fisheye(mut self, field_of_view: f32) -> Self {
+        self.projection = Projection::Fisheye {
+            field_of_view: field_of_view.min(360.),
+        };
+        self.aspect_ratio = 1.;
+        self
+    }
+
+    pub fn with_cube_map(mut self) -> Self {
+        self.projection = Projection::CubeMap;
+        self.aspect_ratio = 3. / 2.;
+        self
+    }
+
+    // Basis vectors for the camera coordinate frame: u points right, v up and w backwards (the
+    // camera looks along -w)
+    pub fn basis(&self) -> (Vec3, Vec3, Vec3) {
+        let w = (self.look_from - self.look_at).unit();
+        let mut right = self.up.cross(w);
+
+        // Looking straight along the up vector there is no telling where the top of the image is.
+        // Continue as if the camera tilted there from looking ahead: looking down with y up, the
+        // top of the image points towards -z.
+        if right.length() < 1e-6 {
+            let ahead = Onb::from_w(self.up.unit()).v * w.dot(self.up).signum();
+
+            right = ahead.cross(w);
+        }
+
+        let u = right.unit();
+        let v = w.cross(u);
+
+        // Roll rotates the camera around its view direction
+        let roll = degrees_to_radians(self.roll);
+        let (sin, cos) = roll.sin_cos();
+
+        (cos * u - sin * v, cos * v + sin * u, w)
+    }
+
+    // Direction of the ray through the point (s, t) of the image for panoramic projections, with s
+    // running from 0 at the left to 1 at the right and t from 0 at the top to 1 at the bottom.
+    // None where the image shows nothing, and for projections that use a viewport.
+    pub fn panoramic_direction(&self, s: f32, t: f32) -> Option<Vec3> {
+        let (u, v, w) = self.basis();
+
+        // Direction from coordinates right, up and forward in the camera's frame
+        let world = |right: f32, up: f32, forward: f32| right * u + up * v - forward * w;
+
+        match self.projection {
+            Projection::Equirectangular => {
+                let longitude = (s - 0.5) * 2. * PI;
+                let latitude = (0.5 - t) * PI;
+
+                Some(world(
+                    latitude.cos() * longitude.sin(),
+                    latitude.sin(),
+                    latitude.cos() * longitude.cos(),
+                ))
+            }
+            Projection::Fisheye { field_of_view } => {
+                // Position relative to the circle in the middle of the image
+                let x = (2. * s - 1.) * self.aspect_ratio;
+                let y = 1. - 2. * t;
+                let r = (x * x + y * y).sqrt();
+
+                if r > 1. {
+                    return None;
+                }
+
+                let theta = r * degrees_to_radians(field_of_view) / 2.;
+                let (x, y) = if r > 0. { (x / r, y / r) } else { (0., 0.) };
+
+                Some(world(theta.sin() * x, theta.sin() * y, theta.cos()))
+            }
+            Projection::CubeMap => {
+                let column = ((s * 3.) as usize).min(2);
+                let row = ((t * 2.) as usize).min(1);
+
+                // Position on the face, from -1 to 1
+                let a = (s * 3. - column as f32) * 2. - 1.;
+                let b = 1. - (t * 2. - row as f32) * 2.;
+
+                // Forward, right and up of the face in the camera's frame
+                let (forward, right, up) = match (row, column) {
+                    (0, 0) => (u, w, v),   // Right
+                    (0, 1) => (-u, -w, v), // Left
+                    (0, _) => (v, u, w),   // Up
+                    (_, 0) => (-v, u, -w), // Down
+                    (_, 1) => (-w, u, v),  // Front
+                    (_, _) => (w, -u, v),  // Back
+                };
+
+                Some(forward + a * right + b * up)
+            }
+            Projection::Perspective | Projection::Orthographic { .. } => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        // Looks along -z like the default camera frame
+        Camera::new(
+            String::from("main"),
+            Point::new(0., 0., 0.),
+            Point::new(0., 0., -1.),
+            90.,
+            16. / 9.,
+        )
+    }
+
+    fn assert_direction(direction: Option<Vec3>, expected: Vec3) {
+        let direction = direction.unwrap().unit();
+
+        assert!((direction - expected).length() < 1e-4, "{direction}");
+    }
+
+    #[test]
+    fn it_rolls_around_the_view_direction() {
+        let (u, v, w) = camera().with_roll(90.).basis();
+
+        // Tilted to the right: the top of the image points right, its right side down
+        assert!((v - Vec3::new(1., 0., 0.)).length() < 1e-5);
+        assert!((u - Vec3::new(0., -1., 0.)).length() < 1e-5);
+        assert!((w - Vec3::new(0., 0., 1.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_follows_a_custom_up_vector() {
+        // Z up world, looking along +y
+        let camera = Camera::new(
+            String::from("main"),
+            Point::new(0., 0., 0.),
+            Point::new(0., 1., 0.),
+            90.,
+            1.,
+        )
+        .with_up(Vec3::new(0., 0., 2.));
+
+        let (u, v, _) = camera.basis();
+
+        assert!((v - Vec3::new(0., 0., 1.)).length() < 1e-5);
+        assert!((u - Vec3::new(1., 0., 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_looks_straight_down_and_up() {
+        let looking =
+            |to: Point| Camera::new(String::from("main"), Point::origin(), to, 90., 1.).basis();
+
+        let (u, v, w) = looking(Point::new(0., -1., 0.));
+        assert!((u - Vec3::new(1., 0., 0.)).length() < 1e-5);
+        assert!((v - Vec3::new(0., 0., -1.)).length() < 1e-5);
+        assert!((w - Vec3::new(0., 1., 0.)).length() < 1e-5);
+
+        let (u, v, _) = looking(Point::new(0., 1., 0.));
+        assert!((u - Vec3::new(1., 0., 0.)).length() < 1e-5);
+        assert!((v - Vec3::new(0., 0., 1.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_builds_cameras_from_angles() {
+        let camera = |yaw, pitch, roll| {
+            Camera::from_angles(
+                String::from("main"),
+                Point::origin(),
+                yaw,
+                pitch,
+                roll,
+                90.,
+                1.,
+            )
+        };
+
+        let (_, _, w) = camera(0., 0., 0.).basis();
+        assert!((w - Vec3::new(0., 0., 1.)).length() < 1e-5);
+
+        // Turned right, looking along +x
+        let (_, _, w) = camera(90., 0., 0.).basis();
+        assert!((w - Vec3::new(-1., 0., 0.)).length() < 1e-5);
+
+        // Turned right and looking straight down: the top of the image points along +x
+        let (u, v, w) = camera(90., -90., 0.).basis();
+        assert!((w - Vec3::new(0., 1., 0.)).length() < 1e-5);
+        assert!((v - Vec3::new(1., 0., 0.)).length() < 1e-5);
+        assert!((u - Vec3::new(0., 0., 1.)).length() < 1e-5);
+
+        let (_, v, _) = camera(0., 0., 30.).basis();
+        assert!((v - Vec3::new(0.5, 0.75f32.sqrt(), 0.)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_covers_all_directions_with_equirectangular_projection() {
+        let camera = camera().with_equirectangular();
+
+        assert_eq!(camera.aspect_ratio, 2.);
+        assert_direction(camera.panoramic_direction(0.5, 0.5), Vec3::new(0., 0., -1.));
+        assert_direction(camera.panoramic_direction(0.75, 0.5), Vec3::new(1., 0., 0.));
+        assert_direction(camera.panoramic_direction(0., 0.5), Vec3::new(0., 0., 1.));
+        assert_direction(camera.panoramic_direction(0.3, 0.), Vec3::new(0., 1., 0.));
+    }
+
+    #[test]
+    fn it_spaces_fisheye_angles_evenly() {
+        let camera = camera().with_fisheye(360.);
+
+        assert_direction(camera.panoramic_direction(0.5, 0.5), Vec3::new(0., 0., -1.));
+        // Halfway to the edge of the circle is 90 degrees off, the edge looks backwards
+        assert_direction(camera.panoramic_direction(0.75, 0.5), Vec3::new(1., 0., 0.));
+        assert_direction(camera.panoramic_direction(0.5, 0.), Vec3::new(0., 0., 1.));
+        assert_eq!(camera.panoramic_direction(0., 0.), None);
+    }
+
+    #[test]
+    fn it_lays_out_the_faces_of_a_cube_map() {
+        let camera = camera().with_cube_map();
+
+        let center = |column: f32, row: f32| {
+            camera.panoramic_direction((column + 0.5) / 3., (row + 0.5) / 2.)
+        };
+
+        assert_direction(center(0., 0.), Vec3::new(1., 0., 0.));
+        assert_direction(center(1., 0.), Vec3::new(-1., 0., 0.));
+        assert_direction(center(2., 0.), Vec3::new(0., 1., 0.));
+        assert_direction(center(0., 1.), Vec3::new(0., -1., 0.));
+        assert_direction(center(1., 1.), Vec3::new(0., 0., -1.));
+        assert_direction(center(2., 1.), Vec3::new(0., 0., 1.));
+
+        // Neighbouring faces meet: the right edge of the front face is the left edge of the
+        // right face
+        let right_edge_of_front = Vec3::new(1., 0., -1.).unit();
+        assert_direction(
+            camera.panoramic_direction(2. / 3. - 1e-6, 0.75),
+            right_edge_of_front,
+        );
+        assert_direction(camera.panoramic_direction(0., 0.25), right_edge_of_front);
+    }
+}
@@ -0,0 +1,80 @@
+// Exposure value the multiplier is calibrated to: f/2.8 at 1/30s (or f/16 at 1s) and ISO 100. This
+// is an evenly lit interior, about as bright as the sky in our scenes.
+const REFERENCE_EXPOSURE_VALUE: i32 = 8;
+
+const REFERENCE_ISO: f32 = 100.;
+
+// Settings of a physical camera, which set how bright the image comes out the way photographers
+// are used to instead of an arbitrary multiplier. Like on a real camera they come with side
+// effects:
+// - the f-stop sets the size of the aperture: wide apertures (low f-stops) let in more light but
+//   keep less of the scene in focus (depth of field)
+// - the shutter speed sets how long the shutter is open: long exposures let in more light, but
+//   anything moving in that time is blurred (motion blur)
+// - ISO is the sensitivity of the sensor, it only scales the image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exposure {
+    pub f_stop: f32,        // Focal length of the lens over the diameter of its aperture
+    pub shutter_speed: f32, // Seconds the shutter is open
+    pub iso: f32,
+    // Focal length of the lens in world units, 0.05 is a standard 50mm lens in a scene in meters
+    pub lens_focal_length: f32,
+}
+
+impl Exposure {
+    pub fn new(f_stop: f32, shutter_speed: f32, iso: f32) -> Self {
+        Self {
+            f_stop,
+            shutter_speed,
+            iso,
+            lens_focal_length: 0.05,
+        }
+    }
+
+    pub fn with_lens_focal_length(mut self, lens_focal_length: f32) -> Self {
+        self.lens_focal_length = lens_focal_length;
+        self
+    }
+
+    // Diameter of the aperture in world units, light enters the camera through a disk this size
+    pub fn aperture_diameter(&self) -> f32 {
+        self.lens_focal_length / self.f_stop
+    }
+
+    // Scale applied to the light reaching the image. Each stop doubles it: opening up the aperture
+    // by a factor of the square root of 2, keeping the shutter open twice as long or doubling ISO.
+    pub fn multiplier(&self) -> f32 {
+        let reference = 2f32.powi(-REFERENCE_EXPOSURE_VALUE);
+
+        self.shutter_speed / (self.f_stop * self.f_stop) / reference * self.iso / REFERENCE_ISO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_doubles_the_light_with_every_stop() {
+        let reference = Exposure::new(16., 1., 100.);
+
+        assert!((reference.multiplier() - 1.).abs() < 1e-5);
+
+        let settings = [
+            Exposure::new(16. / 2f32.sqrt(), 1., 100.),
+            Exposure::new(16., 2., 100.),
+            Exposure::new(16., 1., 200.),
+        ];
+
+        for exposure in settings {
+            assert!((exposure.multiplier() - 2.).abs() < 1e-4, "{exposure:?}");
+        }
+    }
+
+    #[test]
+    fn it_narrows_the_aperture_with_the_f_stop() {
+        let exposure = Exposure::new(2., 1. / 60., 100.).with_lens_focal_length(0.085);
+
+        assert!((exposure.aperture_diameter() - 0.0425).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,420 @@
+use crate::geometry::ray::Ray;
+use crate::math::vector::{Point, Vec3};
+
+// Prescriptions are written in millimeters, scenes are in meters
+const MILLIMETERS: f32 = 0.001;
+
+// Diagonal of a full frame (36 x 24mm) sensor in meters
+const FULL_FRAME_DIAGONAL: f32 = 0.04327;
+
+// Rays through the center of the film used to measure how much light the lens lets through
+const TRANSMISSION_GRID: usize = 64;
+
+// A single surface of a lens: where light passes from one glass (or air) into the next
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LensElement {
+    // Positive when the surface bulges towards the scene, 0 for a flat aperture stop
+    pub radius: f32,
+    pub thickness: f32, // Distance to the next surface, or the film for the last one
+    pub refractive_index: f32, // Of the glass behind the surface, 1 for air
+    pub aperture: f32,  // Diameter, light outside of it is blocked
+}
+
+// A camera lens made of several glass elements, traced surface by surface the way light really
+// passes through it. Gives what a thin lens can't: bokeh shaped by the elements, vignetting where
+// the elements block light towards the corners and distortion.
+//
+// Works in its own frame: the film is at z = 0 with the lens in front of it and the scene towards
+// -z. The image on the film is upside down, like in a real camera.
+#[derive(Clone, Debug)]
+pub struct LensSystem {
+    pub elements: Vec<LensElement>, // From the front of the lens to the back
+    pub film_diagonal: f32,
+
+    // Share of the light from the rear element reaching the center of the film
+    transmission: f32,
+}
+
+impl LensSystem {
+    pub fn new(elements: Vec<LensElement>) -> Self {
+        let mut lens = Self {
+            elements,
+            film_diagonal: FULL_FRAME_DIAGONAL,
+            transmission: 1.,
+        };
+
+        lens.transmission = lens.measure_transmission();
+        lens
+    }
+
+    // Reads a prescription: one surface per line with its radius, thickness, refractive index and
+    // aperture diameter in millimeters, lines starting with # are comments
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not load lens {path}: {e}"))?;
+
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut elements = vec![];
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let values = line
+                .split_whitespace()
+                .map(|value| value.parse::<f32>())
+                .collect::<Result<Vec<f32>, _>>()
+                .map_err(|e| format!("Invalid lens element on line {}: {e}", number + 1))?;
+
+            let [radius, thickness, refractive_index, aperture] = values[..] else {
+                return Err(format!(
+                    "Lens element on line {} needs 4 values, found {}",
+                    number + 1,
+                    values.len()
+                ));
+            };
+
+            elements.push(LensElement {
+                radius: radius * MILLIMETERS,
+                thickness: thickness * MILLIMETERS,
+                // Some prescriptions use 0 for air
+                refractive_index: if refractive_index == 0. {
+                    1.
+                } else {
+                    refractive_index
+                },
+                aperture: aperture * MILLIMETERS,
+            });
+        }
+
+        if elements.is_empty() {
+            return Err(String::from("Lens has no elements"));
+        }
+
+        Ok(Self::new(elements))
+    }
+
+    pub fn with_film_diagonal(mut self, film_diagonal: f32) -> Self {
+        self.film_diagonal = film_diagonal;
+        self
+    }
+
+    // Focuses on things at `distance` from the film by moving the lens towards or away from it.
+    // Traces a ray from a point at that distance close to the axis, and moves the lens until the
+    // ray comes out crossing the axis right at the film. Moving the lens changes the distance to
+    // the point a little, so this takes a few rounds.
+    pub fn focused(mut self, distance: f32) -> Self {
+        let height = self.elements[0].aperture * 0.05;
+
+        for _ in 0..10 {
+            let object = Point::new(0., 0., -distance);
+            let front = Point::new(height, 0., self.front_z());
+
+            let Some(ray) = self.trace_from_scene(Ray::from_to(object, front)) else {
+                break;
+            };
+
+            // Parallel rays never meet the axis, nothing to focus on
+            if ray.direction.x().abs() < f32::EPSILON {
+                break;
+            }
+
+            // Where the ray crosses the axis, relative to the film
+            let image = ray.origin.z() - ray.origin.x() / ray.direction.x() * ray.direction.z();
+
+            self.elements.last_mut().unwrap().thickness += image;
+
+            if image.abs() < 1e-7 {
+                break;
+            }
+        }
+
+        self.transmission = self.measure_transmission();
+        self
+    }
+
+    // Width and height of the film for an image with this aspect ratio
+    pub fn film_size(&self, aspect_ratio: f32) -> (f32, f32) {
+        let height = self.film_diagonal / (1. + aspect_ratio * aspect_ratio).sqrt();
+
+        (height * aspect_ratio, height)
+    }
+
+    // Ray leaving the front of the lens for light reaching the point (s, t) on the film, with s
+    // running from 0 at the left of the image to 1 at the right and t from 0 at the top to 1 at the
+    // bottom. `rear` is a point in the unit disk, picking where the ray passes the rear element.
+    // None when the ray is blocked inside the lens.
+    pub fn ray(&self, s: f32, t: f32, aspect_ratio: f32, rear: Vec3) -> Option<Ray> {
+        let (width, height) = self.film_size(aspect_ratio);
+
+        // The lens flips the image, so the film is read the other way around
+        let film = Point::new((0.5 - s) * width, (t - 0.5) * height, 0.);
+
+        self.ray_from_film(film, rear)
+    }
+
+    // Makes up for the light the lens blocks, so the center of the image is as bright as with a
+    // pinhole camera. Towards the corners less light gets through: that is the vignetting.
+    pub fn brightness(&self) -> f32 {
+        if self.transmission > 0. {
+            1. / self.transmission
+        } else {
+            0.
+        }
+    }
+
+    fn ray_from_film(&self, film: Point, rear: Vec3) -> Option<Ray> {
+        let last = self.elements.last().unwrap();
+        let rear = Point::new(0., 0., -last.thickness) + rear * (last.aperture / 2.);
+
+        self.trace_from_film(Ray::from_to(film, rear))
+    }
+
+    // Distance of the front surface in front of the film
+    fn front_z(&self) -> f32 {
+        -self
+            .elements
+            .iter()
+            .map(|element| element.thickness)
+            .sum::<f32>()
+    }
+
+    fn trace_from_film(&self, ray: Ray) -> Option<Ray> {
+        let mut ray = Ray::new(ray.origin, ray.direction.unit());
+        let mut z = 0.;
+
+        for i in (0..self.elements.len()).rev() {
+            z -= self.elements[i].thickness;
+
+            let outside = if i > 0 {
+                self.elements[i - 1].refractive_index
+            } else {
+                1.
+            };
+
+            ray = self.pass(i, z, ray, self.elements[i].refractive_index, outside)?;
+        }
+
+        Some(ray)
+    }
+
+    fn trace_from_scene(&self, ray: Ray) -> Option<Ray> {
+        let mut ray = Ray::new(ray.origin, ray.direction.unit());
+        let mut z = self.front_z();
+        let mut refractive_index = 1.;
+
+        for (i, element) in self.elements.iter().enumerate() {
+            ray = self.pass(i, z, ray, refractive_index, element.refractive_index)?;
+
+            z += element.thickness;
+            refractive_index = element.refractive_index;
+        }
+
+        Some(ray)
+    }
+
+    // Takes the ray through the surface of element `i` at `z`, from glass with the first
+    // refractive index into glass with the second
+    fn pass(&self, i: usize, z: f32, ray: Ray, from: f32, into: f32) -> Option<Ray> {
+        let element = &self.elements[i];
+
+        let (t, normal) = if element.radius == 0. {
+            // The aperture stop is a hole in a flat plate
+            let t = (z - ray.origin.z()) / ray.direction.z();
+
+            (t, Vec3::new(0., 0., 1.))
+        } else {
+            Self::intersect_surface(element.radius, z, &ray)?
+        };
+
+        if t <= 0. || !t.is_finite() {
+            return None;
+        }
+
+        let p = ray.at(t);
+
+        if p.x() * p.x() + p.y() * p.y() > element.aperture * element.aperture / 4. {
+            return None;
+        }
+
+        // Keep the normal on the side the ray comes from, as refract expects
+        let normal = if normal.dot(ray.direction) > 0. {
+            -normal
+        } else {
+            normal
+        };
+
+        // The light is reflected back inside the glass, it never makes it out of the lens
+        let cos_theta = -ray.direction.dot(normal);
+        let ratio = from / into;
+
+        if ratio * ratio * (1. - cos_theta * cos_theta) > 1. {
+            return None;
+        }
+
+        Some(Ray::new(p, ray.direction.refract(&normal, ratio).unit()))
+    }
+
+    // The surface is part of a sphere with its center on the axis, the ray hits the part of it
+    // around the axis at `z`
+    fn intersect_surface(radius: f32, z: f32, ray: &Ray) -> Option<(f32, Vec3)> {
+        let center = Point::new(0., 0., z + radius);
+        let oc = ray.origin - center;
+
+        let a = ray.direction.dot(ray.direction);
+        let half_b = oc.dot(ray.direction);
+        let c = oc.dot(oc) - radius * radius;
+
+        let discriminant = half_b * half_b - a * c;
+
+        if discriminant < 0. {
+            return None;
+        }
+
+        let near = (-half_b - discriminant.sqrt()) / a;
+        let far = (-half_b + discriminant.sqrt()) / a;
+
+        // Which of the two hits is on the right side of the sphere depends on where the ray comes
+        // from and which way the surface bulges
+        let t = if (ray.direction.z() > 0.) != (radius < 0.) {
+            near
+        } else {
+            far
+        };
+
+        Some((t, (ray.at(t) - center).unit()))
+    }
+
+    // Share of rays from the center of the film towards the rear element that make it through
+    fn measure_transmission(&self) -> f32 {
+        let mut total = 0;
+        let mut passed = 0;
+
+        for i in 0..TRANSMISSION_GRID {
+            for j in 0..TRANSMISSION_GRID {
+                let x = (i as f32 + 0.5) / TRANSMISSION_GRID as f32 * 2. - 1.;
+                let y = (j as f32 + 0.5) / TRANSMISSION_GRID as f32 * 2. - 1.;
+
+                if x * x + y * y >= 1. {
+                    continue;
+                }
+
+                total += 1;
+
+                if self
+                    .ray_from_film(Point::zero(), Vec3::new(x, y, 0.))
+                    .is_some()
+                {
+                    passed += 1;
+                }
+            }
+        }
+
+        passed as f32 / total as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOUBLE_GAUSS: &str = include_str!("../../lenses/double_gauss_50mm.txt");
+
+    fn double_gauss() -> LensSystem {
+        LensSystem::parse(DOUBLE_GAUSS).unwrap()
+    }
+
+    #[test]
+    fn it_parses_a_prescription_in_millimeters() {
+        let lens = double_gauss();
+
+        assert_eq!(lens.elements.len(), 11);
+        assert!((lens.elements[0].radius - 0.029475).abs() < 1e-6);
+        assert!((lens.elements[5].aperture - 0.0171).abs() < 1e-6);
+        assert_eq!(lens.elements[5].radius, 0.);
+    }
+
+    #[test]
+    fn it_loads_a_prescription_file() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/lenses/double_gauss_50mm.txt");
+        let lens = LensSystem::load(path).unwrap();
+
+        assert_eq!(lens.elements.len(), double_gauss().elements.len());
+        assert!(LensSystem::load("lenses/does_not_exist.txt").is_err());
+    }
+
+    #[test]
+    fn it_rejects_malformed_prescriptions() {
+        assert!(LensSystem::parse("# nothing here").is_err());
+        assert!(LensSystem::parse("10 2 1.5").is_err());
+        assert!(LensSystem::parse("10 2 glass 20").is_err());
+    }
+
+    #[test]
+    fn it_moves_the_lens_out_to_focus_closer() {
+        let far = double_gauss().focused(100.);
+        let near = double_gauss().focused(1.);
+
+        let back_focus = |lens: &LensSystem| lens.elements.last().unwrap().thickness;
+
+        // Roughly the 50mm focal length minus the distance from the back of the lens to its
+        // principal plane
+        assert!(back_focus(&far) > 0.02 && back_focus(&far) < 0.05);
+        assert!(back_focus(&near) > back_focus(&far));
+    }
+
+    #[test]
+    fn it_brings_rays_from_the_focus_distance_together_on_the_film() {
+        let lens = double_gauss().focused(2.);
+
+        // Rays from the middle of the film through different parts of the lens all meet again
+        // 2 meters in front of it
+        for rear in [Vec3::new(0.3, 0., 0.), Vec3::new(0., -0.5, 0.)] {
+            let ray = lens.ray(0.5, 0.5, 1.5, rear).unwrap();
+            let t = (-2. - ray.origin.z()) / ray.direction.z();
+            let p = ray.at(t);
+
+            assert!(p.x().abs() < 0.01 && p.y().abs() < 0.01, "{p}");
+        }
+    }
+
+    #[test]
+    fn it_flips_the_image() {
+        let lens = double_gauss().focused(2.);
+
+        // The right of the image sees the right of the scene, through the left of the film
+        let ray = lens.ray(0.75, 0.5, 1.5, Vec3::zero()).unwrap();
+
+        assert!(ray.direction.x() > 0.);
+        assert!(ray.direction.z() < 0.);
+    }
+
+    #[test]
+    fn it_lets_less_light_through_towards_the_corners() {
+        let lens = double_gauss().focused(2.);
+
+        let count = |s: f32, t: f32| {
+            let mut passed = 0;
+
+            for i in 0..20 {
+                for j in 0..20 {
+                    let rear = Vec3::new(i as f32 / 10. - 0.95, j as f32 / 10. - 0.95, 0.);
+
+                    if rear.length() < 1. && lens.ray(s, t, 1.5, rear).is_some() {
+                        passed += 1;
+                    }
+                }
+            }
+
+            passed
+        };
+
+        assert!(count(0., 0.) < count(0.5, 0.5));
+    }
+}
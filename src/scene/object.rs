@@ -1,6 +1,9 @@
+use crate::geometry::cutout::AlphaMask;
 use crate::geometry::ray::Ray;
 use crate::material::base::Material;
+use crate::math::constants::INFINITY;
 use crate::math::numbers::Interval;
+use crate::math::onb::Onb;
 use crate::math::vector::{Point, Vec3};
 
 // Parent for anything that is part of a scene and can have an effect on the rendered output
@@ -8,37 +11,56 @@ pub trait Object {
     fn material(&self) -> &dyn Material;
     fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>);
     fn describe(&self) -> String;
+
+    // Every point where the ray crosses the object's surface along the whole (infinite) line of
+    // the ray, ordered by t. For a closed object the crossings alternate between entering (front
+    // face) and exiting (back face), which is what constructive solid geometry builds on.
+    //
+    // The default implementation keeps asking for the next hit beyond the previous one.
+    fn hits_along(&self, ray: &Ray) -> Vec<Hit> {
+        let mut hits = vec![];
+        let mut t_min = -INFINITY;
+
+        while let (true, Some(hit)) = self.hit_by(ray, Interval::new(t_min, INFINITY)) {
+            t_min = hit.t;
+            hits.push(hit);
+        }
+
+        hits
+    }
+
+    // Opacity mask cutting holes in the object, see Cutout
+    fn alpha_mask(&self) -> Option<&AlphaMask> {
+        None
+    }
 }
 
 // Represents a Ray hitting an object
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Hit {
     pub p: Point,         // Point at which Object is hit
     pub normal: Vec3,     // The normal to the Object at the hit point
     pub t: f32,           // The Ray parameter at which it reaches the object
     pub front_face: bool, // Whether the hit was from the outside (true) or the inside (false) of the object
+    pub u: f32,           // Surface coordinates of the hit point for textures, both between 0 and 1
+    pub v: f32,
+    pub tangent: Vec3, // Unit vector along the surface in the direction u increases, for normal maps
 }
 
 impl Hit {
     pub(crate) fn new(ray: &Ray, p: Point, outward_normal: Vec3, t: f32) -> Self {
         let front_face = Self::detect_front_face(ray, outward_normal);
 
-        let normal: Vec3;
-        if front_face {
-            normal = outward_normal;
-
-            ray_debug!(
-                "Ray hit the object at an angle of {}°",
-                normal.angle_between(ray.direction).to_degrees(),
-            );
+        let normal = if front_face {
+            outward_normal
         } else {
-            normal = -outward_normal;
+            -outward_normal
+        };
 
-            ray_debug!(
-                "Ray hit the object at an angle of {}°",
-                normal.angle_between(ray.direction).to_degrees(),
-            );
-        }
+        ray_debug!(
+            "Ray hit the object at an angle of {}°",
+            normal.angle_between(ray.direction).to_degrees(),
+        );
 
         ray_debug!(
             "Hit at {:?}, front face? {}, outward normal {}, normal {}",
@@ -53,14 +75,35 @@ impl Hit {
             normal,
             t,
             front_face,
+            u: 0.,
+            v: 0.,
+            // Objects without texture coordinates get any direction along the surface
+            tangent: Onb::from_w(outward_normal.unit()).u,
         }
     }
 
-    fn detect_front_face(ray: &Ray, outward_normal: Vec3) -> bool {
-        if ray.direction.dot(outward_normal) < 0. {
-            true
+    // Objects that can be textured set the surface coordinates of the hit point
+    pub fn with_uv(mut self, u: f32, v: f32) -> Self {
+        self.u = u;
+        self.v = v;
+        self
+    }
+
+    pub fn with_tangent(mut self, tangent: Vec3) -> Self {
+        self.tangent = tangent;
+        self
+    }
+
+    // The normal pointing out of the object, regardless of which side the ray came from
+    pub fn outward_normal(&self) -> Vec3 {
+        if self.front_face {
+            self.normal
         } else {
-            false
+            -self.normal
         }
     }
+
+    fn detect_front_face(ray: &Ray, outward_normal: Vec3) -> bool {
+        ray.direction.dot(outward_normal) < 0.
+    }
 }
@@ -2,11 +2,13 @@ use super::object::{Hit, Object};
 use crate::geometry::ray::{Ray, T_MAX, T_MIN};
 use crate::math::numbers::Interval;
 use crate::scene::camera::Camera;
+use crate::volume::fog::Fog;
 
 pub struct Scene {
     pub name: String,
     pub cameras: Vec<Camera>,
     pub objects: Vec<Box<dyn Object>>,
+    pub fog: Option<Fog>, // Scene-wide participating medium, if any
 }
 
 impl Scene {
@@ -14,33 +16,148 @@ impl Scene {
         self.cameras.iter().find(|c| c.name == *name)
     }
 
-    // TODO: write tests
-    pub fn trace(&self, ray: &Ray) -> (Option<&Box<dyn Object>>, Option<Hit>) {
+    pub fn trace(&self, ray: &Ray) -> (Option<&dyn Object>, Option<Hit>) {
         ray_debug!("Tracing ray {} through the scene", ray);
 
         let t_min = T_MIN;
         let mut t_max = T_MAX;
-        let mut closest: (Option<&Box<dyn Object>>, Option<Hit>) = (None, None);
+        let mut closest: (Option<&dyn Object>, Option<Hit>) = (None, None);
 
         for obj in self.objects.iter() {
-            match obj.hit_by(ray, Interval::new(t_min, t_max)) {
-                (true, Some(hit_params)) => {
-                    ray_debug!("Object hit: {}", obj.describe());
-
-                    if hit_params.t < t_max {
-                        ray_debug!("This object is closest so far");
-
-                        // Any subsequent hits need to be closer to the ray's origin than this one
-                        // (we want to find the first thing the Ray hits, starting from its origin
-                        // and looking towards its direction)
-                        t_max = hit_params.t;
-                        closest = (Some(obj), Some(hit_params));
-                    }
+            if let Some(hit_params) = Self::first_opaque_hit(obj.as_ref(), ray, t_min, t_max) {
+                ray_debug!("Object hit: {}", obj.describe());
+
+                if hit_params.t < t_max {
+                    ray_debug!("This object is closest so far");
+
+                    // Any subsequent hits need to be closer to the ray's origin than this one
+                    // (we want to find the first thing the Ray hits, starting from its origin
+                    // and looking towards its direction)
+                    t_max = hit_params.t;
+                    closest = (Some(obj.as_ref()), Some(hit_params));
                 }
-                _ => (),
             }
         }
 
         closest
     }
+
+    // First hit on the object that is not cut away by its alpha mask. Where the mask is
+    // see-through the ray carries on looking for the next hit on the same object, e.g. the inside
+    // of a sphere seen through a hole.
+    fn first_opaque_hit(obj: &dyn Object, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit> {
+        let mut t_min = t_min;
+
+        while let (true, Some(hit)) = obj.hit_by(ray, Interval::new(t_min, t_max)) {
+            match obj.alpha_mask() {
+                Some(mask) if !mask.is_opaque(&hit) => {
+                    ray_debug!("Hit at t {} cut away by alpha mask", hit.t);
+
+                    t_min = hit.t;
+                }
+                _ => return Some(hit),
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::cutout::{AlphaMask, AlphaMode, Cutout};
+    use crate::geometry::plane::Plane;
+    use crate::geometry::sphere::Sphere;
+    use crate::material::diffuse::Lambert;
+    use crate::math::vector::{Color, Point, Vec3};
+    use crate::texture::checker::Checker;
+    use crate::texture::solid::SolidColor;
+
+    fn lambert() -> Box<Lambert> {
+        Box::new(Lambert::new(Color::new(0.5, 0.5, 0.5)))
+    }
+
+    // Unit sphere at z = -5 behind a plane at z = -2 with the given alpha mask
+    fn scene_with_mask(mask: AlphaMask) -> Scene {
+        let plane = Plane::new(Point::new(0., 0., -2.), Vec3::new(0., 0., 1.), lambert());
+
+        Scene {
+            name: String::from("Cutout"),
+            cameras: vec![],
+            objects: vec![
+                Box::new(Cutout::new(Box::new(plane), mask)),
+                Box::new(Sphere::new(Point::new(0., 0., -5.), 1., lambert())),
+            ],
+            fog: None,
+        }
+    }
+
+    fn hit_distance(scene: &Scene, x: f32) -> Option<f32> {
+        let ray = Ray::new(Point::new(x, 0.5, 0.), Vec3::new(0., 0., -1.));
+
+        scene.trace(&ray).1.map(|hit| hit.t)
+    }
+
+    #[test]
+    fn it_traces_through_cut_away_parts() {
+        // Opaque in every other unit cube along x
+        let scene = scene_with_mask(AlphaMask::new(
+            Box::new(Checker::new(
+                1.,
+                Box::new(SolidColor::new_scalar(1.)),
+                Box::new(SolidColor::new_scalar(0.)),
+            )),
+            AlphaMode::Threshold(0.5),
+        ));
+
+        assert_eq!(hit_distance(&scene, 0.5), Some(2.));
+
+        // Through the hole onto the sphere behind it
+        let t = hit_distance(&scene, -0.5).unwrap();
+        assert!(t > 4. && t < 5.);
+    }
+
+    #[test]
+    fn it_continues_along_the_same_object() {
+        // Fully see-through front half of a sphere, the ray hits its back half from the inside
+        let sphere = Sphere::new(Point::new(0., 0., -5.), 1., lambert());
+        let scene = Scene {
+            name: String::from("Cutout"),
+            cameras: vec![],
+            objects: vec![Box::new(Cutout::new(
+                Box::new(sphere),
+                AlphaMask::new(
+                    Box::new(Checker::new(
+                        5.,
+                        Box::new(SolidColor::new_scalar(1.)),
+                        Box::new(SolidColor::new_scalar(0.)),
+                    )),
+                    AlphaMode::Threshold(0.5),
+                ),
+            ))],
+            fog: None,
+        };
+
+        let ray = Ray::new(Point::origin(), Vec3::new(0., 0., -1.));
+        let hit = scene.trace(&ray).1.unwrap();
+
+        assert!((hit.t - 6.).abs() < 1e-4);
+        assert!(!hit.front_face);
+    }
+
+    #[test]
+    fn it_lets_rays_through_by_opacity() {
+        let scene = scene_with_mask(AlphaMask::new(
+            Box::new(SolidColor::new_scalar(0.3)),
+            AlphaMode::Stochastic,
+        ));
+
+        let samples = 10000;
+        let stopped = (0..samples)
+            .filter(|_| hit_distance(&scene, 0.).unwrap() == 2.)
+            .count();
+
+        assert!((stopped as f32 / samples as f32 - 0.3).abs() < 0.02);
+    }
 }
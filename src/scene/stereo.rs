@@ -0,0 +1,188 @@
+use crate::scene::camera::Camera;
+
+// How the eyes of a stereo rig converge on the point the rig looks at
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Convergence {
+    // Both eyes turn inwards to look at the same point. Simple, but the viewports are tilted
+    // against each other, which distorts the image towards the edges (keystoning).
+    ToeIn,
+    // Both eyes look straight ahead and their viewports are shifted sideways so they overlap at
+    // the convergence distance. No keystoning, preferred for VR.
+    OffAxis,
+}
+
+// How the views of both eyes are put in one output image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StereoLayout {
+    SideBySide, // Left eye on the left half, right eye on the right half
+    TopBottom,  // Left eye on the top half, right eye on the bottom half
+}
+
+// Stereoscopic rig: renders a camera's view from two eyes set apart from the camera's position, for
+// VR headsets and 3D displays. Things at the camera's focal length (where it looks at) appear at
+// the screen, nearer things in front of it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stereo {
+    pub interocular_distance: f32, // Distance between the eyes in world units
+    pub convergence: Convergence,
+    pub layout: StereoLayout,
+}
+
+impl Stereo {
+    pub fn new(interocular_distance: f32, convergence: Convergence, layout: StereoLayout) -> Self {
+        Self {
+            interocular_distance,
+            convergence,
+            layout,
+        }
+    }
+
+    // Cameras for the left and right eye, plain cameras that can be rendered like any other
+    pub fn eyes(&self, camera: &Camera) -> [Camera; 2] {
+        let (u, _, _) = camera.basis();
+
+        [("left", -0.5), ("right", 0.5)].map(|(name, side)| {
+            let offset = side * self.interocular_distance * u;
+
+            let mut eye = camera.clone();
+            eye.name = format!("{}-{}", camera.name, name);
+            eye.stereo = None;
+            eye.look_from = camera.look_from + offset;
+
+            match self.convergence {
+                Convergence::ToeIn => {
+                    eye.focal_length = (camera.look_at - eye.look_from).length();
+                }
+                Convergence::OffAxis => {
+                    // Looking parallel to the camera, with the viewport moved back in front of it
+                    eye.look_at = camera.look_at + offset;
+                    eye.viewport_shift = camera.viewport_shift - side * self.interocular_distance;
+                }
+            }
+
+            eye
+        })
+    }
+
+    // Width of the view of each eye in an output image `image_w` wide. Side by side, both eyes get
+    // the same whole number of pixels, so an odd width is rounded down: the output comes out one
+    // pixel narrower than asked for.
+    pub fn view_width(&self, image_w: u32) -> u32 {
+        match self.layout {
+            StereoLayout::SideBySide => image_w / 2,
+            StereoLayout::TopBottom => image_w,
+        }
+    }
+
+    // Size of the output image holding both views, with each view image_w x image_h
+    pub fn output_size(&self, image_w: u32, image_h: u32) -> (u32, u32) {
+        match self.layout {
+            StereoLayout::SideBySide => (2 * image_w, image_h),
+            StereoLayout::TopBottom => (image_w, 2 * image_h),
+        }
+    }
+
+    // Where the view of the left (0) or right (1) eye starts in the output image
+    pub fn view_offset(&self, eye: usize, image_w: u32, image_h: u32) -> (u32, u32) {
+        match self.layout {
+            StereoLayout::SideBySide => (eye as u32 * image_w, 0),
+            StereoLayout::TopBottom => (0, eye as u32 * image_h),
+        }
+    }
+
+    // The eye whose view shows pixel (x, y) of the output image, and where the pixel is in that view
+    pub fn view_at(&self, x: u32, y: u32, image_w: u32, image_h: u32) -> (usize, u32, u32) {
+        let eye = match self.layout {
+            StereoLayout::SideBySide => (x >= image_w) as usize,
+            StereoLayout::TopBottom => (y >= image_h) as usize,
+        };
+        let (offset_x, offset_y) = self.view_offset(eye, image_w, image_h);
+
+        (eye, x - offset_x, y - offset_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vector::{Point, Vec3};
+    use crate::scene::viewport::Viewport;
+
+    fn camera() -> Camera {
+        Camera::new(
+            String::from("main"),
+            Point::new(0., 0., 0.),
+            Point::new(0., 0., -2.),
+            60.,
+            1.,
+        )
+    }
+
+    #[test]
+    fn it_sets_the_eyes_apart() {
+        let rig = Stereo::new(0.1, Convergence::ToeIn, StereoLayout::SideBySide);
+        let [left, right] = rig.eyes(&camera());
+
+        assert_eq!(left.name, "main-left");
+        assert_eq!(left.look_from, Point::new(-0.05, 0., 0.));
+        assert_eq!(right.look_from, Point::new(0.05, 0., 0.));
+
+        // Both turned towards the camera's point of interest
+        assert_eq!(left.look_at, Point::new(0., 0., -2.));
+        assert_eq!(right.look_at, Point::new(0., 0., -2.));
+    }
+
+    #[test]
+    fn it_overlaps_off_axis_viewports_at_the_convergence_distance() {
+        let camera = camera();
+        let rig = Stereo::new(0.1, Convergence::OffAxis, StereoLayout::TopBottom);
+
+        let center = |camera: &Camera| {
+            let viewport = Viewport::from(camera, 100);
+
+            viewport.first_pixel - 0.5 * (viewport.delta_u + viewport.delta_v)
+                + 0.5 * (viewport.u + viewport.v)
+        };
+
+        for eye in rig.eyes(&camera) {
+            // Looking straight ahead, at a viewport in the same place as the camera's
+            assert_eq!(eye.look_at - eye.look_from, Vec3::new(0., 0., -2.));
+            assert!((center(&eye) - center(&camera)).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn it_lays_out_both_views() {
+        let rig = Stereo::new(0.1, Convergence::OffAxis, StereoLayout::SideBySide);
+
+        assert_eq!(rig.view_width(200), 100);
+        assert_eq!(rig.output_size(100, 50), (200, 50));
+        assert_eq!(rig.view_offset(1, 100, 50), (100, 0));
+
+        let rig = Stereo::new(0.1, Convergence::OffAxis, StereoLayout::TopBottom);
+
+        assert_eq!(rig.output_size(100, 50), (100, 100));
+        assert_eq!(rig.view_offset(1, 100, 50), (0, 50));
+    }
+
+    #[test]
+    fn it_finds_the_eye_showing_a_pixel() {
+        let rig = Stereo::new(0.1, Convergence::OffAxis, StereoLayout::SideBySide);
+
+        assert_eq!(rig.view_at(10, 20, 50, 40), (0, 10, 20));
+        assert_eq!(rig.view_at(60, 20, 50, 40), (1, 10, 20));
+
+        let rig = Stereo::new(0.1, Convergence::OffAxis, StereoLayout::TopBottom);
+
+        assert_eq!(rig.view_at(10, 50, 50, 40), (1, 10, 10));
+    }
+
+    #[test]
+    fn it_rounds_odd_widths_down_side_by_side() {
+        let rig = Stereo::new(0.1, Convergence::OffAxis, StereoLayout::SideBySide);
+        let view_w = rig.view_width(101);
+
+        assert_eq!(view_w, 50);
+        assert_eq!(rig.output_size(view_w, 40), (100, 40));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::math::angles::degrees_to_radians;
 use crate::math::vector::{Point, Vec3};
-use crate::scene::camera::Camera;
+use crate::scene::camera::{Camera, Projection};
 
 // In-scene representation of the rendered image.
-// Located in front of the Camera center at the focal length distance.
+// Located in front of the Camera center at the focal length distance for perspective cameras.
+// Orthographic cameras have the viewport go through the camera center, rays start on it.
 #[derive(Debug)]
 pub struct Viewport {
     // Target image resolution in pixels
@@ -37,15 +38,30 @@ impl Viewport {
         // the camera defines because pixels are ints and may not have the exact target aspect ratio
         let viewport_aspect_ratio = image_w as f32 / image_h as f32;
 
-        let theta = degrees_to_radians(camera.field_of_view);
-        let h = (theta / 2.).tan();
-        let viewport_height = 2. * h * camera.focal_length;
-        let viewport_width = viewport_height * viewport_aspect_ratio;
+        let (viewport_width, viewport_height, distance) = match camera.projection {
+            // Panoramic projections don't use the viewport, but a perspective one keeps its
+            // numbers meaningful for debugging
+            Projection::Perspective
+            | Projection::Equirectangular
+            | Projection::Fisheye { .. }
+            | Projection::CubeMap => {
+                let theta = degrees_to_radians(camera.field_of_view);
+                let h = (theta / 2.).tan();
+                let viewport_height = 2. * h * camera.focal_length;
+
+                (
+                    viewport_height * viewport_aspect_ratio,
+                    viewport_height,
+                    camera.focal_length,
+                )
+            }
+            Projection::Orthographic { view_width } => {
+                (view_width, view_width / viewport_aspect_ratio, 0.)
+            }
+        };
 
         // Basis vectors for camera coordinate frame
-        let basis_w = (camera.look_from - camera.look_at).unit();
-        let basis_u = camera.up.cross(basis_w).unit();
-        let basis_v = basis_w.cross(basis_u);
+        let (basis_u, basis_v, basis_w) = camera.basis();
 
         // Vectors spanning width and height of the viewport
         let viewport_u = viewport_width * basis_u;
@@ -56,8 +72,10 @@ impl Viewport {
         let delta_v = viewport_v / image_h as f32;
 
         // Steps from the camera's center to the top left of the viewport
-        let viewport_origin =
-            camera.look_from - (camera.focal_length * basis_w) - viewport_u / 2. - viewport_v / 2.;
+        let viewport_origin = camera.look_from - (distance * basis_w)
+            + camera.viewport_shift * basis_u
+            - viewport_u / 2.
+            - viewport_v / 2.;
 
         // Position of the first pixel in the viewport (first ray to trace)
         let first_pixel = viewport_origin + 0.5 * (delta_u + delta_v);
@@ -76,3 +94,41 @@ impl Viewport {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera::new(
+            String::from("main"),
+            Point::new(0., 0., 2.),
+            Point::new(0., 0., -1.),
+            90.,
+            2.,
+        )
+    }
+
+    #[test]
+    fn it_sizes_the_viewport_by_field_of_view() {
+        let viewport = Viewport::from(&camera(), 200);
+
+        // 90 degrees: as high as twice the distance to the viewport
+        assert_eq!(viewport.image_h, 100);
+        assert!((viewport.h - 6.).abs() < 1e-4);
+        assert!((viewport.w - 12.).abs() < 1e-4);
+        assert!((viewport.first_pixel.z() - -1.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn it_sizes_an_orthographic_viewport_by_view_width() {
+        let viewport = Viewport::from(&camera().with_orthographic(4.), 200);
+
+        assert!((viewport.w - 4.).abs() < 1e-4);
+        assert!((viewport.h - 2.).abs() < 1e-4);
+
+        // The viewport goes through the camera center
+        assert!((viewport.first_pixel.z() - 2.).abs() < 1e-4);
+        assert!((viewport.first_pixel.x() - (-2. + 0.01)).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::material::diffuse::Lambert;
+use crate::material::metal::Metal;
+use crate::math::vector::Color;
+use crate::scene::aperture::{Aperture, ApertureMask};
+use crate::scene::camera::Camera;
+use crate::scene::exposure::Exposure;
+use crate::scene::object::Object;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// An orb up close in focus, with small polished balls scattered far behind it on dark ground. Out
+// of focus, the sky reflected in each ball turns into a highlight in the shape of the aperture.
+pub fn generate() -> Scene {
+    let look_from = Point::new(0., 0.4, 1.2);
+    let look_at = Point::new(0., 0.14, 0.);
+
+    let camera = |name: &str, aperture: Aperture| {
+        Camera::new(String::from(name), look_from, look_at, 25., 16. / 9.)
+            .with_exposure(Exposure::new(1.4, 1. / 125., 800.).with_lens_focal_length(0.085))
+            .with_aperture(aperture)
+    };
+
+    // Six straight blades, slightly turned like a stopped down lens
+    let hexagon = Aperture::Polygon {
+        blades: 6,
+        rotation: 15.,
+    };
+
+    // A heart drawn into a mask, the classic bokeh filter cut from black card
+    let heart = ApertureMask::from_fn(64, |x, y| {
+        let (x, y) = (x * 1.2, y * 1.2 + 0.2);
+        let shape = (x * x + y * y - 1.).powi(3) - x * x * y * y * y;
+
+        if shape <= 0. { 1. } else { 0. }
+    })
+    .expect("Heart shaped aperture lets light through");
+
+    // Or a mask loaded from an image, white where the light gets through
+    let star = ApertureMask::load(concat!(env!("CARGO_MANIFEST_DIR"), "/apertures/star.png"))
+        .expect("Star shaped aperture loads");
+
+    let cameras = vec![
+        camera("main", Aperture::Circle),
+        camera("hexagon", hexagon),
+        camera("heart", Aperture::Mask(heart)),
+        camera("star", Aperture::Mask(star)),
+    ];
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -1000., -1.),
+        1000.,
+        Box::from(Lambert::new(Color::new(0.01, 0.01, 0.012))),
+    );
+
+    let orb = Sphere::new(
+        Point::new(0., 0.12, 0.),
+        0.12,
+        Box::from(Lambert::new(Color::new(0.1, 0.04, 0.03))),
+    );
+
+    let mut objects: Vec<Box<dyn Object>> = vec![Box::new(ground_sphere), Box::new(orb)];
+
+    // Scattered in a loose grid, far enough behind the orb to be well out of focus
+    for i in 0..9 {
+        for j in 0..4 {
+            let x = -2.4 + i as f32 * 0.6 + (j % 2) as f32 * 0.3;
+            let z = -2. - j as f32 * 0.8;
+
+            objects.push(Box::new(Sphere::new(
+                Point::new(x, 0.025, z),
+                0.025,
+                Box::from(Metal::new(Color::new(0.95, 0.9, 0.8), 0.)),
+            )));
+        }
+    }
+
+    Scene {
+        name: String::from("Bokeh in the shape of the aperture"),
+        cameras,
+        objects,
+        fog: None,
+    }
+}
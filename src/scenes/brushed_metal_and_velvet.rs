@@ -0,0 +1,68 @@
+use crate::geometry::plane::Plane;
+use crate::material::conductor::Conductor;
+use crate::material::diffuse::Lambert;
+use crate::material::sheen::Sheen;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::camera::Camera;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// Brushed aluminium next to velvet and satin, on a floor of brushed aluminium
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.5, 1.5),
+        Point::new(0., 0., -1.),
+        50.,
+        16. / 9.,
+    );
+
+    // Brushed along the plane's tangent, which stretches reflections towards the camera
+    let floor = Plane::new(
+        Point::new(0., -0.5, 0.),
+        Vec3::new(0., 1., 0.),
+        Box::from(Conductor::new_aluminium(0.05).with_anisotropy(0.5)),
+    );
+
+    // Brushed around the sphere, from west to east
+    let brushed = Sphere::new(
+        Point::new(-0.9, -0.1, -1.2),
+        0.4,
+        Box::from(Conductor::new_aluminium(0.1).with_anisotropy(0.45)),
+    );
+
+    let velvet = Sphere::new(
+        Point::new(0., -0.1, -1.2),
+        0.4,
+        Box::from(Sheen::new_velvet(Color::new(0.5, 0.05, 0.1))),
+    );
+
+    let satin = Sphere::new(
+        Point::new(0.9, -0.1, -1.2),
+        0.4,
+        Box::from(Sheen::new(
+            Color::new(0.1, 0.2, 0.5),
+            Color::new(0.8, 0.85, 1.),
+            0.6,
+        )),
+    );
+
+    // Matte backdrop for the metal to reflect
+    let backdrop = Plane::new(
+        Point::new(0., 0., -3.),
+        Vec3::new(0., 0., 1.),
+        Box::from(Lambert::new(Color::new(0.6, 0.55, 0.5))),
+    );
+
+    Scene {
+        name: String::from("Brushed metal and velvet"),
+        cameras: vec![camera],
+        objects: vec![
+            Box::new(floor),
+            Box::new(brushed),
+            Box::new(velvet),
+            Box::new(satin),
+            Box::new(backdrop),
+        ],
+        fog: None,
+    }
+}
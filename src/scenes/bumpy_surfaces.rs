@@ -0,0 +1,90 @@
+use crate::geometry::plane::Plane;
+use crate::material::bump_map::BumpMap;
+use crate::material::coated::Coated;
+use crate::material::metal::Metal;
+use crate::material::normal_map::NormalMap;
+use crate::math::constants::PI;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::camera::Camera;
+use crate::texture::image::ImageTexture;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+use image::{Rgb, RgbImage};
+
+// A dimpled metal ball (normal map) on a rippled floor (bump map), both perfectly smooth geometry
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.5, 1.5),
+        Point::new(0., 0., -1.),
+        50.,
+        16. / 9.,
+    );
+
+    let floor = Plane::new(
+        Point::new(0., -0.5, 0.),
+        Vec3::new(0., 1., 0.),
+        Box::from(
+            BumpMap::new(
+                Box::new(Coated::new_diffuse(Color::new(0.3, 0.4, 0.5))),
+                Box::new(ImageTexture::new(ripples()).with_linear_data()),
+            )
+            .with_strength(0.01),
+        ),
+    )
+    .with_tile_size(3.);
+
+    let ball = Sphere::new(
+        Point::new(0., 0., -1.),
+        0.5,
+        Box::from(NormalMap::new(
+            Box::new(Metal::new(Color::new(0.9, 0.9, 0.9), 0.)),
+            Box::new(ImageTexture::new(dimples()).with_linear_data()),
+        )),
+    );
+
+    Scene {
+        name: String::from("Dimpled ball on a rippled floor"),
+        cameras: vec![camera],
+        objects: vec![Box::new(floor), Box::new(ball)],
+        fog: None,
+    }
+}
+
+// Normal map of round dimples like on a golf ball, twice as wide as high to wrap around a sphere
+fn dimples() -> RgbImage {
+    let cell = 16;
+
+    RgbImage::from_fn(32 * cell, 16 * cell, |x, y| {
+        // Position within the dimple, with y pointing up like v
+        let cx = ((x % cell) as f32 + 0.5) / cell as f32 * 2. - 1.;
+        let cy = 1. - ((y % cell) as f32 + 0.5) / cell as f32 * 2.;
+
+        let normal = if cx * cx + cy * cy < 0.8 {
+            Vec3::new(-cx, -cy, 1.).unit() // Leaning towards the center of the dimple
+        } else {
+            Vec3::new(0., 0., 1.)
+        };
+
+        encode_normal(normal)
+    })
+}
+
+// Height map of circular ripples spreading out from the middle
+fn ripples() -> RgbImage {
+    let size = 256;
+
+    RgbImage::from_fn(size, size, |x, y| {
+        let dx = x as f32 / size as f32 - 0.5;
+        let dy = y as f32 / size as f32 - 0.5;
+        let height = 0.5 + 0.5 * (2. * PI * 6. * (dx * dx + dy * dy).sqrt()).sin();
+        let gray = (height * 255.) as u8;
+
+        Rgb([gray, gray, gray])
+    })
+}
+
+fn encode_normal(normal: Vec3) -> Rgb<u8> {
+    let encode = |component: f32| ((component * 0.5 + 0.5) * 255.).round() as u8;
+
+    Rgb([encode(normal.x()), encode(normal.y()), encode(normal.z())])
+}
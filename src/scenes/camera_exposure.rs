@@ -0,0 +1,74 @@
+use crate::material::diffuse::Lambert;
+use crate::material::metal::Metal;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::camera::Camera;
+use crate::scene::exposure::Exposure;
+use crate::scene::lens::LensSystem;
+use crate::scene::object::Object;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// A row of orbs receding into the distance with a ball flying past, in meters. The cameras trade
+// depth of field against motion blur at the same brightness.
+pub fn generate() -> Scene {
+    let look_from = Point::new(0., 0.2, 1.);
+    let look_at = Point::new(-0.3, 0., -3.); // Focused on the third orb
+
+    // Portrait lens wide open: shallow depth of field, fast shutter freezing the ball
+    let camera = Camera::new(String::from("main"), look_from, look_at, 30., 16. / 9.)
+        .with_exposure(Exposure::new(1.4, 1. / 125., 100.).with_lens_focal_length(0.085));
+
+    // Stopped down: more in focus, but the slower shutter smears the ball out
+    let stopped_down = Camera::new(
+        String::from("stopped-down"),
+        look_from,
+        look_at,
+        30.,
+        16. / 9.,
+    )
+    .with_exposure(Exposure::new(4., 1. / 15., 100.).with_lens_focal_length(0.085));
+
+    // Traced through a real 50mm double Gauss lens wide open at f/2, which darkens the corners and
+    // shapes the bokeh. The lens's own stop sets the depth of field, the exposure's f-stop only the
+    // brightness, so it is set to match.
+    let double_gauss = LensSystem::parse(include_str!("../../lenses/double_gauss_50mm.txt"))
+        .expect("Double Gauss lens prescription is valid");
+    let lens = Camera::new(String::from("lens"), look_from, look_at, 30., 16. / 9.)
+        .with_exposure(Exposure::new(2., 1. / 60., 100.))
+        .with_lens(double_gauss);
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -1000.3, -1.),
+        1000.,
+        Box::from(Lambert::new(Color::new(0.5, 0.6, 0.4))),
+    );
+
+    let mut objects: Vec<Box<dyn Object>> = vec![Box::new(ground_sphere)];
+
+    for i in 0..7 {
+        let hue = i as f32 / 6.;
+        objects.push(Box::new(Sphere::new(
+            Point::new(-1.2 + i as f32 * 0.45, 0., -1. - i as f32),
+            0.3,
+            Box::from(Lambert::new(Color::new(
+                0.8 - 0.6 * hue,
+                0.3,
+                0.2 + 0.6 * hue,
+            ))),
+        )));
+    }
+
+    let ball = Sphere::new(
+        Point::new(-0.9, 0.45, -2.4),
+        0.12,
+        Box::from(Metal::new(Color::new(0.9, 0.9, 0.9), 0.1)),
+    )
+    .with_velocity(Vec3::new(10., 0., 0.));
+    objects.push(Box::new(ball));
+
+    Scene {
+        name: String::from("Depth of field and motion blur"),
+        cameras: vec![camera, stopped_down, lens],
+        objects,
+        fog: None,
+    }
+}
@@ -0,0 +1,76 @@
+use crate::material::diffuse::Lambert;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::volume::density_grid::DensityGrid;
+use crate::volume::grid_medium::GridMedium;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// Density of a puffy cloud shape made of a few overlapping blobs, in grid coordinates (0 to 1)
+fn cloud_density(p: Point) -> f32 {
+    let blobs = [
+        (Point::new(0.5, 0.4, 0.5), 0.3),
+        (Point::new(0.3, 0.35, 0.45), 0.2),
+        (Point::new(0.7, 0.35, 0.55), 0.22),
+        (Point::new(0.55, 0.6, 0.5), 0.2),
+    ];
+
+    let density: f32 = blobs
+        .iter()
+        .map(|(center, radius)| {
+            let falloff = 1. - (p - *center).length() / radius;
+            falloff.max(0.)
+        })
+        .sum();
+
+    // Break up the smooth blobs a bit so the edges look wispy
+    let wisps = 0.75 + 0.25 * ((p.x() * 37.).sin() * (p.y() * 41.).sin() * (p.z() * 43.).sin());
+
+    (density * wisps).min(1.)
+}
+
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.5, 1.5),
+        Point::new(0., 0.5, -1.5),
+        60.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.3, 0.5, 0.2))),
+    );
+
+    let grid = DensityGrid::from_fn(48, 32, 32, cloud_density);
+
+    // Clouds scatter strongly forward and absorb very little
+    let cloud = GridMedium::new(
+        grid,
+        Point::new(-1.5, -0.2, -2.5),
+        Point::new(1.5, 1.8, -0.5),
+        0.05,
+        12.,
+        0.6,
+    );
+
+    // A small puff drifting behind it, from a grid stored in a file
+    let puff_grid = DensityGrid::load(concat!(env!("CARGO_MANIFEST_DIR"), "/volumes/puff.raw"))
+        .expect("Puff density grid loads");
+    let puff = GridMedium::new(
+        puff_grid,
+        Point::new(1.6, 0.9, -3.5),
+        Point::new(2.8, 1.8, -2.3),
+        0.05,
+        12.,
+        0.6,
+    );
+
+    Scene {
+        name: String::from("Clouds from density grids floating above the ground"),
+        cameras: vec![camera],
+        objects: vec![Box::new(ground_sphere), Box::new(cloud), Box::new(puff)],
+        fog: None,
+    }
+}
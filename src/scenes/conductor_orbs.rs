@@ -0,0 +1,49 @@
+use crate::material::conductor::Conductor;
+use crate::material::diffuse::Lambert;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::scene::object::Object;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.8, 1.2),
+        Point::new(0., 0., -1.2),
+        60.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.5, 0.5, 0.5))),
+    );
+
+    // Front row polished, back row rough
+    let mut objects: Vec<Box<dyn Object>> = vec![Box::new(ground_sphere)];
+
+    for (z, roughness) in [(-1., 0.05), (-2.2, 0.4)] {
+        let metals = [
+            Conductor::new_gold(roughness),
+            Conductor::new_copper(roughness),
+            Conductor::new_aluminium(roughness),
+            Conductor::new_silver(roughness),
+        ];
+
+        for (i, metal) in metals.into_iter().enumerate() {
+            objects.push(Box::new(Sphere::new(
+                Point::new(-1.5 + i as f32, -0.1, z),
+                0.4,
+                Box::from(metal),
+            )));
+        }
+    }
+
+    Scene {
+        name: String::from("Gold, copper, aluminium and silver orbs, polished and rough"),
+        cameras: vec![camera],
+        objects,
+        fog: None,
+    }
+}
@@ -0,0 +1,87 @@
+use crate::geometry::csg::Csg;
+use crate::geometry::cuboid::Cuboid;
+use crate::material::dielectric::Dielectric;
+use crate::material::diffuse::Lambert;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.5, 1.),
+        Point::new(0., 0., -1.),
+        70.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.8, 0.8, 0.))),
+    );
+
+    // Hollow glass orb: a real shell instead of a second sphere with an inverted refractive index
+    let shell = Csg::difference(
+        Box::new(Sphere::new(
+            Point::new(-1.1, 0., -1.),
+            0.5,
+            Box::from(Dielectric::new_glass()),
+        )),
+        Box::new(Sphere::new(
+            Point::new(-1.1, 0., -1.),
+            0.4,
+            Box::from(Dielectric::new_glass()),
+        )),
+        Box::from(Dielectric::new_glass()),
+    );
+
+    // Biconvex lens: the overlap of two large spheres
+    let lens = Csg::intersection(
+        Box::new(Sphere::new(
+            Point::new(0., 0., -2.6),
+            1.8,
+            Box::from(Dielectric::new_glass()),
+        )),
+        Box::new(Sphere::new(
+            Point::new(0., 0., 0.6),
+            1.8,
+            Box::from(Dielectric::new_glass()),
+        )),
+        Box::from(Dielectric::new_glass()),
+    );
+
+    // Cut-away orb: a box taking a bite out of a sphere
+    let cut_away = Csg::difference(
+        Box::new(Sphere::new(
+            Point::new(1.1, 0., -1.),
+            0.5,
+            Box::from(Lambert::new(Color::new(0.1, 0.2, 0.5))),
+        )),
+        Box::new(Cuboid::new(
+            Point::new(1.1, 0., -1.),
+            Point::new(1.7, 0.6, -0.4),
+            Box::from(Lambert::new(Color::new(0.1, 0.2, 0.5))),
+        )),
+        Box::from(Lambert::new(Color::new(0.1, 0.2, 0.5))),
+    );
+
+    let background_sphere = Sphere::new(
+        Point::new(0., 0., -3.),
+        0.5,
+        Box::from(Lambert::new(Color::new(0.7, 0.2, 0.2))),
+    );
+
+    Scene {
+        name: String::from("Hollow shell, lens and cut-away orb built with CSG"),
+        cameras: vec![camera],
+        objects: vec![
+            Box::new(ground_sphere),
+            Box::new(shell),
+            Box::new(lens),
+            Box::new(cut_away),
+            Box::new(background_sphere),
+        ],
+        fog: None,
+    }
+}
@@ -0,0 +1,100 @@
+use crate::geometry::cutout::{AlphaMask, AlphaMode, Cutout};
+use crate::geometry::plane::Plane;
+use crate::material::diffuse::Lambert;
+use crate::material::metal::Metal;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::camera::Camera;
+use crate::texture::image::ImageTexture;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+use image::{Rgb, RgbImage};
+
+// Orbs behind a wire fence, and a ball cut into a cage. Both the fence and the cage are smooth
+// surfaces with holes cut out by alpha masks.
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0.3, 0.3, 1.),
+        Point::new(0., 0., -1.5),
+        60.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.5, 0.6, 0.3))),
+    );
+
+    // Rotated 45° so the holes of the mesh become diamonds
+    let mut fence_plane = Plane::new(
+        Point::new(0., 0., -0.6),
+        Vec3::new(0., 0., 1.),
+        Box::from(Metal::new(Color::new(0.7, 0.7, 0.7), 0.3)),
+    )
+    .with_tile_size(0.12);
+    fence_plane.tangent = Vec3::new(1., 1., 0.).unit();
+
+    let fence = Cutout::new(
+        Box::new(fence_plane),
+        AlphaMask::new(
+            Box::new(ImageTexture::new(wire_mesh()).with_linear_data()),
+            AlphaMode::Threshold(0.5),
+        ),
+    );
+
+    let cage = Cutout::new(
+        Box::new(Sphere::new(
+            Point::new(-0.7, -0.1, -1.8),
+            0.4,
+            Box::from(Lambert::new(Color::new(0.8, 0.3, 0.1))),
+        )),
+        AlphaMask::new(
+            Box::new(ImageTexture::new(bars()).with_linear_data()),
+            AlphaMode::Threshold(0.5),
+        ),
+    );
+
+    let orb = Sphere::new(
+        Point::new(0.6, 0., -2.),
+        0.5,
+        Box::from(Lambert::new(Color::new(0.1, 0.3, 0.8))),
+    );
+
+    Scene {
+        name: String::from("Orbs behind a wire fence"),
+        cameras: vec![camera],
+        objects: vec![
+            Box::new(ground_sphere),
+            Box::new(fence),
+            Box::new(cage),
+            Box::new(orb),
+        ],
+        fog: None,
+    }
+}
+
+// One tile of wire mesh: opaque border, see-through middle
+fn wire_mesh() -> RgbImage {
+    RgbImage::from_fn(32, 32, |x, y| {
+        let wire = !(3..=28).contains(&x) || !(3..=28).contains(&y);
+
+        if wire {
+            Rgb([255, 255, 255])
+        } else {
+            Rgb([0, 0, 0])
+        }
+    })
+}
+
+// Bars running from pole to pole with a ring around the equator
+fn bars() -> RgbImage {
+    RgbImage::from_fn(256, 128, |x, y| {
+        let bar = x % 32 < 6 || (61..67).contains(&y);
+
+        if bar {
+            Rgb([255, 255, 255])
+        } else {
+            Rgb([0, 0, 0])
+        }
+    })
+}
@@ -22,5 +22,6 @@ pub fn generate() -> Scene {
         name: String::from("Single basic diffuse sphere"),
         cameras: vec![camera],
         objects: vec![Box::new(sphere)],
+        fog: None,
     }
 }
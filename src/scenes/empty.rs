@@ -15,5 +15,6 @@ pub fn generate() -> Scene {
         name: String::from("Empty"),
         cameras: vec![camera],
         objects: vec![],
+        fog: None,
     }
 }
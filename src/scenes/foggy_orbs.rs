@@ -0,0 +1,71 @@
+use crate::geometry::cuboid::Cuboid;
+use crate::material::diffuse::Lambert;
+use crate::material::metal::Metal;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::volume::constant_medium::ConstantMedium;
+use crate::volume::fog::Fog;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.3, 1.),
+        Point::new(0., 0., -1.5),
+        70.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.8, 0.8, 0.8))),
+    );
+
+    let metal_sphere = Sphere::new(
+        Point::new(1., 0., -1.5),
+        0.5,
+        Box::from(Metal::new(Color::new(0.8, 0.6, 0.2), 0.1)),
+    );
+
+    let lambert_sphere = Sphere::new(
+        Point::new(0., 0., -4.),
+        0.5,
+        Box::from(Lambert::new(Color::new(0.1, 0.2, 0.5))),
+    );
+
+    // Puff of white smoke, the boundary sphere's own material is never used
+    let smoke_ball = ConstantMedium::new(
+        Box::new(Sphere::new(
+            Point::new(-1., 0., -1.5),
+            0.5,
+            Box::from(Lambert::new(Color::new(1., 1., 1.))),
+        )),
+        4.,
+        Color::new(0.9, 0.9, 0.9),
+    );
+
+    // Block of dark, dense smoke
+    let smoke_block = ConstantMedium::new(
+        Box::new(Cuboid::new(
+            Point::new(-0.3, -0.5, -2.5),
+            Point::new(0.3, 0.4, -1.9),
+            Box::from(Lambert::new(Color::new(1., 1., 1.))),
+        )),
+        10.,
+        Color::new(0.2, 0.2, 0.2),
+    );
+
+    Scene {
+        name: String::from("Orbs and smoke in a foggy landscape"),
+        cameras: vec![camera],
+        objects: vec![
+            Box::new(ground_sphere),
+            Box::new(metal_sphere),
+            Box::new(lambert_sphere),
+            Box::new(smoke_ball),
+            Box::new(smoke_block),
+        ],
+        fog: Some(Fog::new(0.05, Color::new(0.9, 0.9, 0.9), 10.)),
+    }
+}
@@ -0,0 +1,70 @@
+use crate::geometry::cuboid::Cuboid;
+use crate::material::dielectric::Dielectric;
+use crate::material::diffuse::Lambert;
+use crate::material::rough_dielectric::RoughDielectric;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::scene::object::Object;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.3, 1.),
+        Point::new(0., 0., -1.),
+        70.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.8, 0.8, 0.8))),
+    );
+
+    // Increasingly frosted glass from left to right
+    let clear = Sphere::new(
+        Point::new(-1.1, 0., -1.),
+        0.5,
+        Box::from(Dielectric::new_glass()),
+    );
+    let satin = Sphere::new(
+        Point::new(0., 0., -1.),
+        0.5,
+        Box::from(RoughDielectric::new_frosted_glass(0.15)),
+    );
+    let frosted = Sphere::new(
+        Point::new(1.1, 0., -1.),
+        0.5,
+        Box::from(RoughDielectric::new_frosted_glass(0.5)),
+    );
+
+    // Colorful stripes behind the orbs to show off the blur
+    let stripes = [
+        Color::new(0.8, 0.1, 0.1),
+        Color::new(0.1, 0.6, 0.1),
+        Color::new(0.1, 0.2, 0.8),
+    ];
+
+    let mut objects: Vec<Box<dyn Object>> = vec![
+        Box::new(ground_sphere),
+        Box::new(clear),
+        Box::new(satin),
+        Box::new(frosted),
+    ];
+
+    for i in 0..9 {
+        objects.push(Box::new(Cuboid::new(
+            Point::new(-2.25 + i as f32 * 0.5, -0.5, -2.6),
+            Point::new(-2.0 + i as f32 * 0.5, 1.2, -2.4),
+            Box::from(Lambert::new(stripes[i % 3])),
+        )));
+    }
+
+    Scene {
+        name: String::from("Clear, satin and frosted glass orbs in front of stripes"),
+        cameras: vec![camera],
+        objects,
+        fog: None,
+    }
+}
@@ -0,0 +1,71 @@
+use crate::geometry::cuboid::Cuboid;
+use crate::geometry::instance::Instance;
+use crate::material::diffuse::Lambert;
+use crate::material::metal::Metal;
+use crate::math::transform::Transform;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::camera::Camera;
+use crate::scene::object::Object;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+use std::rc::Rc;
+
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 1., 1.5),
+        Point::new(0., 0., -1.5),
+        60.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.8, 0.8, 0.8))),
+    );
+
+    // A single unit cube and a single unit sphere, each placed in the scene several times
+    let cube: Rc<dyn Object> = Rc::new(Cuboid::new(
+        Point::new(-0.5, -0.5, -0.5),
+        Point::new(0.5, 0.5, 0.5),
+        Box::from(Lambert::new(Color::new(0.7, 0.2, 0.2))),
+    ));
+
+    let orb: Rc<dyn Object> = Rc::new(Sphere::new(
+        Point::origin(),
+        1.,
+        Box::from(Metal::new(Color::new(0.8, 0.8, 0.8), 0.1)),
+    ));
+
+    let mut objects: Vec<Box<dyn Object>> = vec![Box::new(ground_sphere)];
+
+    // A row of cubes, each turned a bit further around the vertical axis
+    for i in 0..5 {
+        let transform = Transform::identity()
+            .scale(Vec3::new(0.4, 0.4, 0.4))
+            .rotate_y(i as f32 * 15.)
+            .translate(Vec3::new(-1.6 + i as f32 * 0.8, -0.3, -2.));
+
+        objects.push(Box::new(Instance::new(cube.clone(), transform)));
+    }
+
+    // A flattened and a stretched orb, tilted towards each other
+    let flattened = Transform::identity()
+        .scale(Vec3::new(0.4, 0.15, 0.4))
+        .rotate_z(-20.)
+        .translate(Vec3::new(-0.6, -0.2, -1.));
+    let stretched = Transform::identity()
+        .scale(Vec3::new(0.15, 0.4, 0.15))
+        .rotate_z(20.)
+        .translate(Vec3::new(0.6, -0.1, -1.));
+
+    objects.push(Box::new(Instance::new(orb.clone(), flattened)));
+    objects.push(Box::new(Instance::new(orb.clone(), stretched)));
+
+    Scene {
+        name: String::from("Instanced cubes and orbs with different transforms"),
+        cameras: vec![camera],
+        objects,
+        fog: None,
+    }
+}
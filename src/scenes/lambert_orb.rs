@@ -22,5 +22,6 @@ pub fn generate() -> Scene {
         name: String::from("Single lambert sphere"),
         cameras: vec![camera],
         objects: vec![Box::new(sphere)],
+        fog: None,
     }
 }
@@ -46,5 +46,6 @@ pub fn generate() -> Scene {
             Box::new(left_sphere),
             Box::new(right_sphere),
         ],
+        fog: None,
     }
 }
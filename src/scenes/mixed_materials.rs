@@ -0,0 +1,68 @@
+use crate::material::coated::Coated;
+use crate::material::diffuse::Lambert;
+use crate::material::metal::Metal;
+use crate::material::mix::MixMaterial;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::texture::checker::Checker;
+use crate::texture::solid::SolidColor;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// Looks built from the basic materials: varnished wood, glossy paint and dusty metal
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.4, 1.2),
+        Point::new(0., 0., -1.),
+        55.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.6, 0.6, 0.6))),
+    );
+
+    // Amber varnish over a brown diffuse base
+    let varnished_wood =
+        Coated::new_diffuse(Color::new(0.45, 0.25, 0.1)).with_tint(Color::new(1., 0.9, 0.7));
+
+    let glossy_paint = Coated::new_diffuse(Color::new(0.1, 0.25, 0.7));
+
+    // Patches of dust on polished metal, the checker decides how dusty each patch is
+    let dusty_metal = MixMaterial::new(
+        Box::new(Metal::new(Color::new(0.8, 0.8, 0.85), 0.05)),
+        Box::new(Lambert::new(Color::new(0.6, 0.55, 0.5))),
+        0.,
+    )
+    .with_weight_texture(Box::new(Checker::new(
+        0.1,
+        Box::new(SolidColor::new_scalar(0.15)),
+        Box::new(SolidColor::new_scalar(0.6)),
+    )));
+
+    Scene {
+        name: String::from("Varnished wood, glossy paint and dusty metal orbs"),
+        cameras: vec![camera],
+        objects: vec![
+            Box::new(ground_sphere),
+            Box::new(Sphere::new(
+                Point::new(-1.05, 0., -1.),
+                0.5,
+                Box::from(varnished_wood),
+            )),
+            Box::new(Sphere::new(
+                Point::new(0., 0., -1.),
+                0.5,
+                Box::from(glossy_paint),
+            )),
+            Box::new(Sphere::new(
+                Point::new(1.05, 0., -1.),
+                0.5,
+                Box::from(dusty_metal),
+            )),
+        ],
+        fog: None,
+    }
+}
@@ -24,5 +24,6 @@ pub fn generate() -> Scene {
         name: String::from("Small sphere above ground"),
         cameras: vec![camera],
         objects: vec![Box::new(sphere_one), Box::new(sphere_two)],
+        fog: None,
     }
 }
@@ -62,5 +62,6 @@ pub fn generate() -> Scene {
             Box::new(left_sphere_inside),
             Box::new(right_sphere),
         ],
+        fog: None,
     }
 }
@@ -0,0 +1,108 @@
+use crate::geometry::cone::Cone;
+use crate::geometry::cuboid::Cuboid;
+use crate::geometry::cylinder::Cylinder;
+use crate::geometry::torus::Torus;
+use crate::material::diffuse::Lambert;
+use crate::material::metal::Metal;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::camera::Camera;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 1.5, 2.),
+        Point::new(0., 0., -1.5),
+        60.,
+        16. / 9.,
+    );
+
+    // Technical views without perspective: an isometric view (looking down along the diagonal of
+    // a cube) and a straight view from the front. Orthographic rays start on the camera's plane,
+    // the front view is kept above the ground so none of them start inside it.
+    let center = Point::new(-0.2, -0.2, -1.6);
+    let isometric = Camera::new(
+        String::from("isometric"),
+        center + Vec3::new(3., 3., 3.),
+        center,
+        60.,
+        16. / 9.,
+    )
+    .with_orthographic(5.);
+    let front_center = Point::new(-0.2, 0.75, -1.6);
+    let front = Camera::new(
+        String::from("front"),
+        front_center + Vec3::new(0., 0., 5.),
+        front_center,
+        60.,
+        16. / 9.,
+    )
+    .with_orthographic(4.4);
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.8, 0.8, 0.8))),
+    );
+
+    let cuboid = Cuboid::new(
+        Point::new(-2.2, -0.5, -2.),
+        Point::new(-1.4, 0.3, -1.2),
+        Box::from(Lambert::new(Color::new(0.7, 0.2, 0.2))),
+    );
+
+    let cylinder = Cylinder::new(
+        Point::new(-0.5, -0.5, -1.6),
+        0.35,
+        0.9,
+        Box::from(Lambert::new(Color::new(0.2, 0.6, 0.2))),
+    );
+
+    let cone = Cone::new(
+        Point::new(0.5, -0.5, -1.6),
+        0.4,
+        1.,
+        Box::from(Lambert::new(Color::new(0.2, 0.3, 0.7))),
+    );
+
+    let torus = Torus::new(
+        Point::new(1.7, -0.3, -1.6),
+        0.45,
+        0.2,
+        Box::from(Metal::new(Color::new(0.8, 0.6, 0.2), 0.2)),
+    );
+
+    // Plan view straight down, the top of the image points away from the main camera
+    let top = Camera::new(
+        String::from("top"),
+        center + Vec3::new(0., 5., 0.),
+        center,
+        60.,
+        16. / 9.,
+    )
+    .with_orthographic(5.);
+
+    // Tilted like a dutch angle shot, from the main camera's position
+    let dutch = Camera::from_angles(
+        String::from("dutch"),
+        Point::new(0., 1.5, 2.),
+        0.,
+        -30.,
+        20.,
+        60.,
+        16. / 9.,
+    );
+
+    Scene {
+        name: String::from("Box, cylinder, cone and torus on a Lambert ground"),
+        cameras: vec![camera, isometric, front, top, dutch],
+        objects: vec![
+            Box::new(ground_sphere),
+            Box::new(cuboid),
+            Box::new(cylinder),
+            Box::new(cone),
+            Box::new(torus),
+        ],
+        fog: None,
+    }
+}
@@ -0,0 +1,91 @@
+use crate::material::principled::Principled;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::scene::object::Object;
+use crate::scene::stereo::{Convergence, Stereo, StereoLayout};
+use crate::texture::checker::Checker;
+use crate::texture::solid::SolidColor;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// Orbs showing off the parameters of the principled material, on a checkered floor
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.6, 1.6),
+        Point::new(0., 0., -1.),
+        55.,
+        16. / 9.,
+    );
+
+    // Side by side view for each eye of a VR headset, focused on the orbs
+    let stereo = Camera::new(
+        String::from("stereo"),
+        Point::new(0., 0.6, 1.6),
+        Point::new(0., -0.15, -1.2),
+        55.,
+        1.,
+    )
+    .with_stereo(Stereo::new(
+        0.1,
+        Convergence::OffAxis,
+        StereoLayout::SideBySide,
+    ));
+
+    // Panoramic views from just in front of the orbs, e.g. for VR previews or reflection probes
+    let panorama = |name: &str| {
+        Camera::new(
+            String::from(name),
+            Point::new(0., -0.1, -0.5),
+            Point::new(0., -0.1, -1.2),
+            90.,
+            1.,
+        )
+    };
+    let equirectangular = panorama("equirectangular").with_equirectangular();
+    let fisheye = panorama("fisheye").with_fisheye(180.);
+    let cube_map = panorama("cube-map").with_cube_map();
+
+    // The checker drives both the color and the roughness of the floor: shiny dark tiles, matte
+    // light ones
+    let floor = Principled::new(Color::new(0.5, 0.5, 0.5))
+        .with_base_color_texture(Box::new(Checker::new(
+            0.5,
+            Box::new(SolidColor::new(Color::new(0.05, 0.05, 0.06))),
+            Box::new(SolidColor::new(Color::new(0.8, 0.8, 0.75))),
+        )))
+        .with_roughness_texture(Box::new(Checker::new(
+            0.5,
+            Box::new(SolidColor::new_scalar(0.1)),
+            Box::new(SolidColor::new_scalar(0.9)),
+        )));
+
+    let ground_sphere = Sphere::new(Point::new(0., -100.5, -1.), 100., Box::from(floor));
+
+    let red = Color::new(0.8, 0.1, 0.1);
+    let orbs = [
+        Principled::new(red).with_roughness(0.8),  // Matte plastic
+        Principled::new(red).with_roughness(0.15), // Glossy plastic
+        Principled::new(red).with_clearcoat(1., 0.02), // Car paint
+        Principled::new(Color::new(0.95, 0.64, 0.54)) // Polished copper
+            .with_metallic(1.)
+            .with_roughness(0.25),
+        Principled::new(Color::new(0.2, 0.1, 0.4)).with_sheen(1.), // Velvet
+    ];
+
+    let mut objects: Vec<Box<dyn Object>> = vec![Box::new(ground_sphere)];
+
+    for (i, orb) in orbs.into_iter().enumerate() {
+        objects.push(Box::new(Sphere::new(
+            Point::new(-1.6 + i as f32 * 0.8, -0.15, -1.2),
+            0.35,
+            Box::from(orb),
+        )));
+    }
+
+    Scene {
+        name: String::from("Principled material orbs"),
+        cameras: vec![camera, stereo, equirectangular, fisheye, cube_map],
+        objects,
+        fog: None,
+    }
+}
@@ -0,0 +1,81 @@
+use crate::geometry::csg::Csg;
+use crate::geometry::cuboid::Cuboid;
+use crate::geometry::instance::Instance;
+use crate::material::dielectric::Dielectric;
+use crate::material::diffuse::Lambert;
+use crate::math::transform::Transform;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::camera::Camera;
+use crate::scene::object::Object;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+use std::rc::Rc;
+
+// A glass prism and a diamond in front of black and white stripes, the edges of the stripes seen
+// through them split into rainbows
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.2, 1.),
+        Point::new(0., 0., -1.),
+        60.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.8, 0.8, 0.8))),
+    );
+
+    // Triangular prism: a long box turned onto its edge, with everything below its middle cut off
+    let diamond_section: Rc<dyn Object> = Rc::new(Cuboid::new(
+        Point::new(-0.5, -0.5, -1.),
+        Point::new(0.5, 0.5, 1.),
+        Box::from(Dielectric::new_bk7()),
+    ));
+    let prism = Csg::intersection(
+        Box::new(Instance::new(
+            diamond_section,
+            Transform::identity()
+                .rotate_z(45.)
+                .rotate_y(20.)
+                .translate(Vec3::new(-0.4, -0.5, -1.2)),
+        )),
+        Box::new(Cuboid::new(
+            Point::new(-3., -0.5, -3.),
+            Point::new(3., 1., 1.),
+            Box::from(Dielectric::new_bk7()),
+        )),
+        Box::from(Dielectric::new_bk7()),
+    );
+
+    let diamond = Sphere::new(
+        Point::new(0.9, -0.15, -1.),
+        0.35,
+        Box::from(Dielectric::new_diamond()),
+    );
+
+    let mut objects: Vec<Box<dyn Object>> =
+        vec![Box::new(ground_sphere), Box::new(prism), Box::new(diamond)];
+
+    for i in 0..12 {
+        let color = if i % 2 == 0 {
+            Color::new(0.9, 0.9, 0.9)
+        } else {
+            Color::new(0.05, 0.05, 0.05)
+        };
+
+        objects.push(Box::new(Cuboid::new(
+            Point::new(-2.4 + i as f32 * 0.4, -0.5, -3.),
+            Point::new(-2.0 + i as f32 * 0.4, 1.5, -2.9),
+            Box::from(Lambert::new(color)),
+        )));
+    }
+
+    Scene {
+        name: String::from("Dispersive glass prism and diamond"),
+        cameras: vec![camera],
+        objects,
+        fog: None,
+    }
+}
@@ -0,0 +1,75 @@
+use crate::material::conductor::Conductor;
+use crate::material::dielectric::Dielectric;
+use crate::material::principled::Principled;
+use crate::material::thin_film::ThinFilm;
+use crate::math::constants::PI;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::texture::checker::Checker;
+use crate::texture::image::ImageTexture;
+use crate::texture::solid::SolidColor;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+use image::{Rgb, RgbImage};
+
+// A soap bubble floating over a checkered floor next to a ball of anodized titanium, both colored
+// by thin-film interference
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.3, 1.5),
+        Point::new(0., 0.1, -1.),
+        50.,
+        16. / 9.,
+    );
+
+    let floor =
+        Principled::new(Color::new(0.5, 0.5, 0.5)).with_base_color_texture(Box::new(Checker::new(
+            0.5,
+            Box::new(SolidColor::new(Color::new(0.1, 0.1, 0.12))),
+            Box::new(SolidColor::new(Color::new(0.8, 0.8, 0.75))),
+        )));
+    let ground_sphere = Sphere::new(Point::new(0., -100.5, -1.), 100., Box::from(floor));
+
+    let film = ThinFilm::new_soap(600.).with_variation(Box::new(
+        ImageTexture::new(drained_film()).with_linear_data(),
+    ));
+    let bubble = Sphere::new(
+        Point::new(-0.35, 0.2, -1.),
+        0.45,
+        Box::from(Dielectric::new_bubble(film)),
+    );
+
+    let titanium = Sphere::new(
+        Point::new(0.6, -0.2, -1.3),
+        0.3,
+        Box::from(Conductor::new_titanium(0.1).with_thin_film(ThinFilm::new(110., 2.4))),
+    );
+
+    Scene {
+        name: String::from("Soap bubble"),
+        cameras: vec![camera],
+        objects: vec![
+            Box::new(ground_sphere),
+            Box::new(bubble),
+            Box::new(titanium),
+        ],
+        fog: None,
+    }
+}
+
+// Relative thickness of the film around the bubble. Gravity drains the soap down, so the film is
+// thin at the top and thick at the bottom, with swirls where it flows.
+fn drained_film() -> RgbImage {
+    let (width, height) = (256, 128);
+
+    RgbImage::from_fn(width, height, |x, y| {
+        let u = x as f32 / width as f32;
+        let v = 1. - y as f32 / height as f32;
+
+        let swirl = 0.08 * (2. * PI * (3. * u + 2. * v)).sin() * (2. * PI * 5. * v).cos();
+        let thickness = (0.2 + 0.8 * (1. - v) + swirl).clamp(0., 1.);
+        let gray = (thickness * 255.) as u8;
+
+        Rgb([gray, gray, gray])
+    })
+}
@@ -0,0 +1,43 @@
+use crate::material::principled::Principled;
+use crate::material::subsurface::Subsurface;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::scene::object::Object;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// Skin, wax and marble orbs, the light softened by scattering around inside them
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.4, 1.4),
+        Point::new(0., 0., -1.),
+        50.,
+        16. / 9.,
+    );
+
+    let floor = Principled::new(Color::new(0.4, 0.4, 0.45)).with_roughness(0.6);
+    let ground_sphere = Sphere::new(Point::new(0., -100.5, -1.), 100., Box::from(floor));
+
+    let orbs = [
+        Subsurface::new_skin(),
+        Subsurface::new_wax(),
+        Subsurface::new_marble(),
+    ];
+
+    let mut objects: Vec<Box<dyn Object>> = vec![Box::new(ground_sphere)];
+
+    for (i, orb) in orbs.into_iter().enumerate() {
+        objects.push(Box::new(Sphere::new(
+            Point::new(-0.9 + i as f32 * 0.9, -0.1, -1.2),
+            0.4,
+            Box::from(orb),
+        )));
+    }
+
+    Scene {
+        name: String::from("Subsurface scattering orbs"),
+        cameras: vec![camera],
+        objects,
+        fog: None,
+    }
+}
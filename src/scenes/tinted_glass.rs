@@ -0,0 +1,42 @@
+use crate::material::dielectric::Dielectric;
+use crate::material::diffuse::Lambert;
+use crate::math::vector::Color;
+use crate::scene::camera::Camera;
+use crate::{geometry::sphere::Sphere, math::vector::Point, scene::scene::Scene};
+
+// The same green bottle glass in orbs of different sizes, the light travels further through the
+// larger ones so their color is deeper
+pub fn generate() -> Scene {
+    let camera = Camera::new(
+        String::from("main"),
+        Point::new(0., 0.4, 1.5),
+        Point::new(0., 0., -1.),
+        60.,
+        16. / 9.,
+    );
+
+    let ground_sphere = Sphere::new(
+        Point::new(0., -100.5, -1.),
+        100.,
+        Box::from(Lambert::new(Color::new(0.8, 0.8, 0.7))),
+    );
+
+    let bottle_glass =
+        || Box::from(Dielectric::new_glass().with_absorption(Color::new(0.4, 0.8, 0.5), 0.5));
+
+    let small = Sphere::new(Point::new(-1.2, -0.35, -1.), 0.15, bottle_glass());
+    let medium = Sphere::new(Point::new(-0.5, -0.2, -1.), 0.3, bottle_glass());
+    let large = Sphere::new(Point::new(0.6, 0.1, -1.), 0.6, bottle_glass());
+
+    Scene {
+        name: String::from("Tinted glass orbs of increasing size"),
+        cameras: vec![camera],
+        objects: vec![
+            Box::new(ground_sphere),
+            Box::new(small),
+            Box::new(medium),
+            Box::new(large),
+        ],
+        fog: None,
+    }
+}
@@ -31,5 +31,6 @@ pub fn generate() -> Scene {
         name: String::from("Two spheres touching"),
         cameras: vec![camera],
         objects: vec![Box::new(sphere_blue), Box::new(sphere_red)],
+        fog: None,
     }
 }
@@ -0,0 +1,16 @@
+use crate::math::vector::{Color, Point};
+
+// Color (or any other material parameter) that varies across a surface. Textures are looked up
+// with the surface coordinates (u, v) of a hit, and the hit point itself for solid textures that
+// fill space instead of being wrapped around a surface.
+pub trait Texture {
+    fn value(&self, u: f32, v: f32, p: Point) -> Color;
+    fn describe(&self) -> String;
+
+    // For parameters like roughness that are a single number, a grayscale texture
+    fn value_scalar(&self, u: f32, v: f32, p: Point) -> f32 {
+        let value = self.value(u, v, p);
+
+        (value.x() + value.y() + value.z()) / 3.
+    }
+}
@@ -0,0 +1,61 @@
+use crate::math::vector::{Color, Point};
+use crate::texture::base::Texture;
+
+// Solid 3D checkerboard: space is divided into cubes that alternate between two textures. Because
+// it uses the hit point and not the surface coordinates it works on any object.
+pub struct Checker {
+    pub scale: f32, // Size of a single cube
+    pub even: Box<dyn Texture>,
+    pub odd: Box<dyn Texture>,
+}
+
+impl Checker {
+    pub fn new(scale: f32, even: Box<dyn Texture>, odd: Box<dyn Texture>) -> Self {
+        Self { scale, even, odd }
+    }
+}
+
+impl Texture for Checker {
+    fn value(&self, u: f32, v: f32, p: Point) -> Color {
+        let cell = (p.x() / self.scale).floor() as i64
+            + (p.y() / self.scale).floor() as i64
+            + (p.z() / self.scale).floor() as i64;
+
+        if cell % 2 == 0 {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "checker of {} and {} with scale {}",
+            self.even.describe(),
+            self.odd.describe(),
+            self.scale
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::solid::SolidColor;
+
+    #[test]
+    fn it_alternates_between_cubes() {
+        let white = Color::new(1., 1., 1.);
+        let black = Color::new(0., 0., 0.);
+        let checker = Checker::new(
+            0.5,
+            Box::new(SolidColor::new(white)),
+            Box::new(SolidColor::new(black)),
+        );
+
+        assert_eq!(checker.value(0., 0., Point::new(0.1, 0.1, 0.1)), white);
+        assert_eq!(checker.value(0., 0., Point::new(0.6, 0.1, 0.1)), black);
+        assert_eq!(checker.value(0., 0., Point::new(0.6, 0.6, 0.1)), white);
+        assert_eq!(checker.value(0., 0., Point::new(-0.1, 0.1, 0.1)), black);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::math::vector::{Color, Point};
+use crate::texture::base::Texture;
+use image::RgbImage;
+
+// Picture wrapped onto a surface using its (u, v) coordinates: u runs left to right and v bottom
+// to top across the image. Blends the four nearest pixels (bilinear filtering) so the texture
+// stays smooth up close.
+pub struct ImageTexture {
+    image: RgbImage,
+    linear: bool, // Holds data (like a normal map) instead of colors, no gamma correction
+}
+
+impl ImageTexture {
+    pub fn new(image: RgbImage) -> Self {
+        Self {
+            image,
+            linear: false,
+        }
+    }
+
+    // For images that hold data instead of colors, like normal maps and height maps
+    pub fn with_linear_data(mut self) -> Self {
+        self.linear = true;
+        self
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let image = image::open(path)
+            .map_err(|e| format!("Could not load texture {path}: {e}"))?
+            .to_rgb8();
+
+        Ok(Self::new(image))
+    }
+
+    // Images are stored gamma corrected, undo the same gamma 2 the image output applies
+    fn gamma_to_linear(component: u8) -> f32 {
+        let gamma = component as f32 / 255.;
+
+        gamma * gamma
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> Color {
+        let pixel = self.image.get_pixel(x, y);
+
+        if self.linear {
+            return Color::new(pixel[0] as f32, pixel[1] as f32, pixel[2] as f32) / 255.;
+        }
+
+        Color::new(
+            Self::gamma_to_linear(pixel[0]),
+            Self::gamma_to_linear(pixel[1]),
+            Self::gamma_to_linear(pixel[2]),
+        )
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f32, v: f32, _p: Point) -> Color {
+        let (w, h) = self.image.dimensions();
+
+        if w == 0 || h == 0 {
+            return Color::new(1., 0., 1.); // Bright magenta to make missing textures stand out
+        }
+
+        // Position in pixels, relative to pixel centers. Image rows go from the top down.
+        let x = (u.clamp(0., 1.) * w as f32 - 0.5).clamp(0., (w - 1) as f32);
+        let y = ((1. - v.clamp(0., 1.)) * h as f32 - 0.5).clamp(0., (h - 1) as f32);
+
+        let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+        let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+        let top = self.pixel(x0, y0) * (1. - fx) + self.pixel(x1, y0) * fx;
+        let bottom = self.pixel(x0, y1) * (1. - fx) + self.pixel(x1, y1) * fx;
+
+        top * (1. - fy) + bottom * fy
+    }
+
+    fn describe(&self) -> String {
+        let (w, h) = self.image.dimensions();
+
+        format!("{w} x {h} image")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn it_wraps_the_image_with_v_pointing_up() {
+        // 2 x 2 image: red and green on top, blue and white at the bottom
+        let mut image = RgbImage::new(2, 2);
+        image.put_pixel(0, 0, Rgb([255, 0, 0]));
+        image.put_pixel(1, 0, Rgb([0, 255, 0]));
+        image.put_pixel(0, 1, Rgb([0, 0, 255]));
+        image.put_pixel(1, 1, Rgb([255, 255, 255]));
+
+        let texture = ImageTexture::new(image);
+        let p = Point::origin();
+
+        assert_eq!(texture.value(0.25, 0.75, p), Color::new(1., 0., 0.));
+        assert_eq!(texture.value(0.75, 0.75, p), Color::new(0., 1., 0.));
+        assert_eq!(texture.value(0.25, 0.25, p), Color::new(0., 0., 1.));
+        assert_eq!(texture.value(1., 0., p), Color::new(1., 1., 1.));
+    }
+
+    #[test]
+    fn it_blends_between_pixels() {
+        let mut image = RgbImage::new(2, 1);
+        image.put_pixel(0, 0, Rgb([0, 0, 0]));
+        image.put_pixel(1, 0, Rgb([255, 255, 255]));
+
+        let texture = ImageTexture::new(image).with_linear_data();
+
+        // Halfway between both pixel centers
+        let value = texture.value(0.5, 0.5, Point::origin());
+        assert!((value - Color::new(0.5, 0.5, 0.5)).length() < 1e-5);
+    }
+
+    #[test]
+    fn it_converts_to_linear_color() {
+        let texture = ImageTexture::new(RgbImage::from_pixel(1, 1, Rgb([128, 128, 128])));
+        let value = texture.value(0.5, 0.5, Point::origin());
+
+        assert!((value.x() - 0.252).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::math::vector::{Color, Point};
+use crate::texture::base::Texture;
+
+// The same color everywhere
+pub struct SolidColor {
+    pub color: Color,
+}
+
+impl SolidColor {
+    pub fn new(color: Color) -> Self {
+        Self { color }
+    }
+
+    // Grayscale value, for scalar parameters
+    pub fn new_scalar(value: f32) -> Self {
+        Self::new(Color::new(value, value, value))
+    }
+}
+
+impl Texture for SolidColor {
+    fn value(&self, _u: f32, _v: f32, _p: Point) -> Color {
+        self.color
+    }
+
+    fn describe(&self) -> String {
+        format!("solid color {}", self.color)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::phase::Isotropic;
+use crate::math::chance::random_f32;
+use crate::math::constants::INFINITY;
+use crate::math::numbers::Interval;
+use crate::math::vector::{Color, Vec3};
+use crate::scene::object::{Hit, Object};
+
+// Volume of homogeneous smoke or fog filling a closed boundary Object.
+//
+// A ray travelling through the medium has a constant chance per unit of distance to hit a
+// particle and scatter, so the distance it travels before scattering is exponentially
+// distributed. Rays that make it through without scattering just continue as if the medium
+// was not there.
+pub struct ConstantMedium {
+    pub boundary: Box<dyn Object>,
+    pub density: f32, // Expected number of scattering events per unit of distance
+    pub phase_function: Box<dyn Material>,
+}
+
+impl ConstantMedium {
+    pub fn new(boundary: Box<dyn Object>, density: f32, albedo: Color) -> Self {
+        Self {
+            boundary,
+            density,
+            phase_function: Box::new(Isotropic::new(albedo)),
+        }
+    }
+
+    // Intervals along the ray (in units of t) in which the ray is inside the boundary
+    fn inside_intervals(&self, ray: &Ray) -> Vec<Interval> {
+        let mut intervals = vec![];
+        let mut entered_at = None;
+
+        for hit in self.boundary.hits_along(ray) {
+            if hit.front_face {
+                entered_at = Some(hit.t);
+            } else {
+                intervals.push(Interval::new(entered_at.unwrap_or(-INFINITY), hit.t));
+                entered_at = None;
+            }
+        }
+
+        intervals
+    }
+}
+
+impl Object for ConstantMedium {
+    fn material(&self) -> &dyn Material {
+        &*self.phase_function
+    }
+
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        let ray_length = ray.direction.length();
+
+        for inside in self.inside_intervals(ray) {
+            // Only the part of the interval that lies within the acceptable range counts
+            let t_enter = inside.min.max(within.min);
+            let t_exit = inside.max.min(within.max);
+
+            if t_enter >= t_exit {
+                continue;
+            }
+
+            let distance_inside = (t_exit - t_enter) * ray_length;
+            let scatter_distance = -(1. - random_f32(0., 1.)).ln() / self.density;
+
+            if scatter_distance < distance_inside {
+                let t = t_enter + scatter_distance / ray_length;
+
+                ray_debug!("Ray scattered inside medium at t {}", t);
+
+                // The normal is meaningless inside a volume, the phase function does not use it
+                return (
+                    true,
+                    Some(Hit {
+                        p: ray.at(t),
+                        normal: Vec3::new(1., 0., 0.),
+                        t,
+                        front_face: true,
+                        u: 0.,
+                        v: 0.,
+                        tangent: Vec3::new(0., 0., 1.),
+                    }),
+                );
+            }
+        }
+
+        (false, None)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Constant medium with density {} inside {}, phase function: {}",
+            self.density,
+            self.boundary.describe(),
+            self.phase_function.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::cuboid::Cuboid;
+    use crate::math::vector::Point;
+
+    // Slab of medium 1 unit thick, the ray crosses it perpendicularly
+    fn slab(density: f32) -> ConstantMedium {
+        ConstantMedium::new(
+            Box::new(Cuboid::new(
+                Point::new(-10., -10., -1.),
+                Point::new(10., 10., 0.),
+                Box::new(Isotropic::new(Color::new(1., 1., 1.))),
+            )),
+            density,
+            Color::new(1., 1., 1.),
+        )
+    }
+
+    #[test]
+    fn it_scatters_inside_its_boundary() {
+        let medium = slab(1.);
+        let ray = Ray::new(Point::new(0., 0., 5.), Vec3::new(0., 0., -1.));
+
+        for _ in 0..100 {
+            if let (true, Some(hit)) = medium.hit_by(&ray, Interval::new(0.001, 100.)) {
+                assert!(hit.t >= 5. && hit.t <= 6.);
+            }
+        }
+    }
+
+    #[test]
+    fn it_transmits_according_to_its_density() {
+        // Chance of crossing the slab without scattering is e^(-density * thickness)
+        let medium = slab(0.5);
+        let ray = Ray::new(Point::new(0., 0., 5.), Vec3::new(0., 0., -2.));
+
+        let samples = 20000;
+        let transmitted = (0..samples)
+            .filter(|_| !medium.hit_by(&ray, Interval::new(0.001, 100.)).0)
+            .count();
+
+        let expected = (-0.5f32).exp();
+        let actual = transmitted as f32 / samples as f32;
+
+        assert!((actual - expected).abs() < 0.02, "{actual} != {expected}");
+    }
+}
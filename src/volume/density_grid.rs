@@ -0,0 +1,201 @@
+use crate::math::vector::Point;
+use std::fs;
+
+// Regular 3D grid of density values, as produced by smoke and cloud simulations.
+//
+// Grids are stored in a simple raw binary format, all values little-endian:
+//  - three u32 values: the grid resolution along x, y and z
+//  - nx * ny * nz f32 values: the densities, x varying fastest, then y, then z
+pub struct DensityGrid {
+    pub nx: usize,
+    pub ny: usize,
+    pub nz: usize,
+    densities: Vec<f32>,
+    pub max_density: f32,
+}
+
+impl DensityGrid {
+    pub fn new(nx: usize, ny: usize, nz: usize, densities: Vec<f32>) -> Result<Self, String> {
+        if nx == 0 || ny == 0 || nz == 0 {
+            return Err(format!("Invalid grid resolution {nx} x {ny} x {nz}"));
+        }
+
+        // A corrupt file can claim a resolution with more voxels than fit in memory
+        let Some(voxels) = nx.checked_mul(ny).and_then(|n| n.checked_mul(nz)) else {
+            return Err(format!("Grid of {nx} x {ny} x {nz} is too large"));
+        };
+
+        if densities.len() != voxels {
+            return Err(format!(
+                "Grid of {nx} x {ny} x {nz} needs {voxels} densities, got {}",
+                densities.len()
+            ));
+        }
+
+        if densities.iter().any(|d| !d.is_finite() || *d < 0.) {
+            return Err(String::from("Densities must be finite and not negative"));
+        }
+
+        let max_density = densities.iter().cloned().fold(0., f32::max);
+
+        Ok(Self {
+            nx,
+            ny,
+            nz,
+            densities,
+            max_density,
+        })
+    }
+
+    // Builds a grid by evaluating a density function at the center of every voxel, the function
+    // receives coordinates between 0 and 1 along each axis
+    pub fn from_fn(nx: usize, ny: usize, nz: usize, density: impl Fn(Point) -> f32) -> Self {
+        let mut densities = Vec::with_capacity(nx * ny * nz);
+
+        for z in 0..nz {
+            for y in 0..ny {
+                for x in 0..nx {
+                    densities.push(density(Point::new(
+                        (x as f32 + 0.5) / nx as f32,
+                        (y as f32 + 0.5) / ny as f32,
+                        (z as f32 + 0.5) / nz as f32,
+                    )));
+                }
+            }
+        }
+
+        Self::new(nx, ny, nz, densities).expect("Density function produced an invalid grid")
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|err| format!("Failed to read grid {path}: {err}"))?;
+
+        Self::from_bytes(&bytes).map_err(|err| format!("Failed to parse grid {path}: {err}"))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_bytes())
+            .map_err(|err| format!("Failed to write grid {path}: {err}"))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < 12 {
+            return Err(String::from("Missing grid resolution header"));
+        }
+
+        let values: Vec<[u8; 4]> = bytes
+            .chunks(4)
+            .map(|chunk| chunk.try_into().map_err(|_| "Truncated value"))
+            .collect::<Result<_, _>>()?;
+
+        let nx = u32::from_le_bytes(values[0]) as usize;
+        let ny = u32::from_le_bytes(values[1]) as usize;
+        let nz = u32::from_le_bytes(values[2]) as usize;
+
+        let densities = values[3..].iter().map(|v| f32::from_le_bytes(*v)).collect();
+
+        Self::new(nx, ny, nz, densities)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12 + self.densities.len() * 4);
+
+        for n in [self.nx, self.ny, self.nz] {
+            bytes.extend((n as u32).to_le_bytes());
+        }
+        for density in self.densities.iter() {
+            bytes.extend(density.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    fn voxel(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.densities[x + self.nx * (y + self.ny * z)]
+    }
+
+    // Density at a position in the grid, with coordinates between 0 and 1 along each axis.
+    // Voxel values sit at the voxel centers and are interpolated trilinearly in between.
+    pub fn density(&self, p: Point) -> f32 {
+        // Position in voxel units, relative to the first voxel center
+        let gx = (p.x() * self.nx as f32 - 0.5).clamp(0., (self.nx - 1) as f32);
+        let gy = (p.y() * self.ny as f32 - 0.5).clamp(0., (self.ny - 1) as f32);
+        let gz = (p.z() * self.nz as f32 - 0.5).clamp(0., (self.nz - 1) as f32);
+
+        let (x0, y0, z0) = (gx as usize, gy as usize, gz as usize);
+        let (x1, y1, z1) = (
+            (x0 + 1).min(self.nx - 1),
+            (y0 + 1).min(self.ny - 1),
+            (z0 + 1).min(self.nz - 1),
+        );
+        let (fx, fy, fz) = (gx - x0 as f32, gy - y0 as f32, gz - z0 as f32);
+
+        let lerp = |a: f32, b: f32, f: f32| a + (b - a) * f;
+
+        let c00 = lerp(self.voxel(x0, y0, z0), self.voxel(x1, y0, z0), fx);
+        let c10 = lerp(self.voxel(x0, y1, z0), self.voxel(x1, y1, z0), fx);
+        let c01 = lerp(self.voxel(x0, y0, z1), self.voxel(x1, y0, z1), fx);
+        let c11 = lerp(self.voxel(x0, y1, z1), self.voxel(x1, y1, z1), fx);
+
+        lerp(lerp(c00, c10, fy), lerp(c01, c11, fy), fz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2 x 1 x 1 grid, empty on the left and dense on the right
+    fn gradient() -> DensityGrid {
+        DensityGrid::new(2, 1, 1, vec![0., 2.]).unwrap()
+    }
+
+    #[test]
+    fn it_interpolates_between_voxel_centers() {
+        let grid = gradient();
+
+        assert_eq!(grid.density(Point::new(0.25, 0.5, 0.5)), 0.);
+        assert_eq!(grid.density(Point::new(0.5, 0.5, 0.5)), 1.);
+        assert_eq!(grid.density(Point::new(0.75, 0.5, 0.5)), 2.);
+        assert_eq!(grid.density(Point::new(0., 0.5, 0.5)), 0.); // Clamped at the edges
+        assert_eq!(grid.density(Point::new(1., 0.5, 0.5)), 2.);
+        assert_eq!(grid.max_density, 2.);
+    }
+
+    #[test]
+    fn it_rejects_invalid_grids() {
+        assert!(DensityGrid::new(2, 2, 1, vec![0., 1., 2.]).is_err());
+        assert!(DensityGrid::new(0, 1, 1, vec![]).is_err());
+        assert!(DensityGrid::new(1, 1, 1, vec![-1.]).is_err());
+        assert!(DensityGrid::from_bytes(&[1, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn it_rejects_grids_too_large_to_count() {
+        assert!(DensityGrid::new(usize::MAX, 2, 1, vec![]).is_err());
+        assert!(DensityGrid::from_bytes(&[0xff; 12]).is_err());
+    }
+
+    #[test]
+    fn it_loads_the_sample_grid() {
+        let grid =
+            DensityGrid::load(concat!(env!("CARGO_MANIFEST_DIR"), "/volumes/puff.raw")).unwrap();
+
+        assert_eq!((grid.nx, grid.ny, grid.nz), (16, 12, 16));
+        assert!(grid.max_density > 0. && grid.max_density <= 1.);
+    }
+
+    #[test]
+    fn it_round_trips_through_files() {
+        let grid = DensityGrid::from_fn(3, 2, 4, |p| p.x() + p.y() * p.z());
+        let path = std::env::temp_dir().join("srt_density_grid_test.raw");
+        let path = path.to_str().unwrap();
+
+        grid.save(path).unwrap();
+        let loaded = DensityGrid::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!((loaded.nx, loaded.ny, loaded.nz), (3, 2, 4));
+        assert_eq!(loaded.densities, grid.densities);
+    }
+}
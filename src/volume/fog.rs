@@ -0,0 +1,41 @@
+use crate::geometry::ray::Ray;
+use crate::math::chance::random_f32;
+use crate::math::vector::{Color, Vec3};
+
+// Homogeneous fog filling the whole scene.
+//
+// Works like a ConstantMedium without a boundary: along every ray segment between two surfaces
+// the ray may hit a fog particle and scatter into a random direction. Rays that escape the scene
+// are fogged over `extent` units of distance only, the sky lies beyond the fog.
+pub struct Fog {
+    pub density: f32,  // Expected number of scattering events per unit of distance
+    pub albedo: Color, // Fraction of light scattered rather than absorbed at each scattering event
+    pub extent: f32,   // Distance over which rays that hit nothing travel through fog
+}
+
+impl Fog {
+    pub fn new(density: f32, albedo: Color, extent: f32) -> Self {
+        Self {
+            density,
+            albedo,
+            extent,
+        }
+    }
+
+    // Samples whether the ray scatters in the fog before it travels `distance` units (the distance
+    // to the surface it hits, if any). Returns the scattered ray and its attenuation if it does.
+    pub fn scatter(&self, ray: &Ray, distance: Option<f32>) -> Option<(Ray, Color)> {
+        let distance = distance.unwrap_or(self.extent);
+        let scatter_distance = -(1. - random_f32(0., 1.)).ln() / self.density;
+
+        if scatter_distance >= distance {
+            return None;
+        }
+
+        ray_debug!("FOG - ray scattered after {} units", scatter_distance);
+
+        let p = ray.at(scatter_distance / ray.direction.length());
+
+        Some((Ray::new(p, Vec3::random_unit()), self.albedo))
+    }
+}
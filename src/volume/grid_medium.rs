@@ -0,0 +1,270 @@
+use crate::geometry::cuboid::slab_test;
+use crate::geometry::ray::Ray;
+use crate::material::base::Material;
+use crate::material::phase::HenyeyGreenstein;
+use crate::math::chance::random_f32;
+use crate::math::numbers::Interval;
+use crate::math::vector::{Color, Point, Vec3};
+use crate::scene::object::{Hit, Object};
+use crate::volume::density_grid::DensityGrid;
+
+// Heterogeneous medium (clouds, simulated smoke) with its density defined by a DensityGrid that is
+// stretched over an axis-aligned box.
+//
+// The density varies along a ray, so the distance to the next scattering event can not be sampled
+// directly like in a ConstantMedium. Delta tracking gets around this by filling up the volume with
+// fictitious particles until it is as dense everywhere as in its densest spot (the majorant).
+// Distances are sampled against that constant majorant, and at each tentative collision the
+// chance that a real particle was hit is the real density over the majorant.
+pub struct GridMedium {
+    pub grid: DensityGrid,
+    pub min: Point, // Corner of the box with the lowest x, y and z coordinates
+    pub max: Point, // Corner of the box with the highest x, y and z coordinates
+
+    // Absorption and scattering coefficients per unit of density
+    pub absorption: f32,
+    pub scattering: f32,
+
+    pub phase_function: Box<dyn Material>,
+}
+
+impl GridMedium {
+    // `anisotropy` is the Henyey-Greenstein g parameter of the medium's phase function
+    pub fn new(
+        grid: DensityGrid,
+        a: Point,
+        b: Point,
+        absorption: f32,
+        scattering: f32,
+        anisotropy: f32,
+    ) -> Self {
+        // At every real collision the light is either absorbed or scattered. Rather than ending
+        // paths on absorption, every collision scatters and the path carries on with the fraction
+        // of light that was scattered.
+        // An empty medium never collides, any albedo will do.
+        let single_scattering_albedo = if absorption + scattering > 0. {
+            scattering / (absorption + scattering)
+        } else {
+            0.
+        };
+
+        Self {
+            grid,
+            min: Point::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z())),
+            max: Point::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z())),
+            absorption,
+            scattering,
+            phase_function: Box::new(HenyeyGreenstein::new(
+                Color::new(1., 1., 1.) * single_scattering_albedo,
+                anisotropy,
+            )),
+        }
+    }
+
+    // Extinction coefficient (absorption + scattering) at a point in world space
+    fn extinction(&self, p: Point) -> f32 {
+        let local = (p - self.min) / (self.max - self.min);
+
+        (self.absorption + self.scattering) * self.grid.density(local)
+    }
+
+    fn majorant(&self) -> f32 {
+        (self.absorption + self.scattering) * self.grid.max_density
+    }
+
+    // The part of the ray (in units of t) that lies within the box and the acceptable range
+    fn inside(&self, ray: &Ray, within: Interval) -> Option<Interval> {
+        let slabs = slab_test(self.min, self.max, ray)?;
+
+        let t_enter = slabs.t_enter.max(within.min);
+        let t_exit = slabs.t_exit.min(within.max);
+
+        if t_enter >= t_exit {
+            return None;
+        }
+
+        Some(Interval::new(t_enter, t_exit))
+    }
+
+    // Samples the t of the next tentative collision with the majorant medium
+    fn step(&self, t: f32, majorant: f32, ray_length: f32) -> f32 {
+        t - (1. - random_f32(0., 1.)).ln() / (majorant * ray_length)
+    }
+
+    // Estimates the fraction of light that makes it through the medium along the ray, without
+    // being absorbed or scattered away, using ratio tracking. Instead of stopping at the first real
+    // collision like delta tracking does, it walks the same tentative collisions all the way
+    // through and multiplies in the chance that each one was fictitious, which gives a smooth
+    // estimate instead of all or nothing.
+    pub fn transmittance(&self, ray: &Ray, within: Interval) -> f32 {
+        let majorant = self.majorant();
+
+        let inside = match self.inside(ray, within) {
+            Some(inside) if majorant > 0. => inside,
+            _ => return 1.,
+        };
+
+        let ray_length = ray.direction.length();
+        let mut transmittance = 1.;
+        let mut t = inside.min;
+
+        loop {
+            t = self.step(t, majorant, ray_length);
+
+            if t >= inside.max {
+                return transmittance;
+            }
+
+            transmittance *= 1. - self.extinction(ray.at(t)) / majorant;
+        }
+    }
+}
+
+impl Object for GridMedium {
+    fn material(&self) -> &dyn Material {
+        &*self.phase_function
+    }
+
+    // Delta tracking: find where the ray collides with a real particle, if it does at all
+    fn hit_by(&self, ray: &Ray, within: Interval) -> (bool, Option<Hit>) {
+        let majorant = self.majorant();
+
+        let inside = match self.inside(ray, within) {
+            Some(inside) if majorant > 0. => inside,
+            _ => return (false, None),
+        };
+
+        let ray_length = ray.direction.length();
+        let mut t = inside.min;
+
+        loop {
+            t = self.step(t, majorant, ray_length);
+
+            if t >= inside.max {
+                return (false, None); // Made it through the medium
+            }
+
+            let p = ray.at(t);
+
+            if random_f32(0., 1.) < self.extinction(p) / majorant {
+                ray_debug!("Ray collided inside grid medium at t {}", t);
+
+                // The normal is meaningless inside a volume, the phase function does not use it
+                return (
+                    true,
+                    Some(Hit {
+                        p,
+                        normal: Vec3::new(1., 0., 0.),
+                        t,
+                        front_face: true,
+                        u: 0.,
+                        v: 0.,
+                        tangent: Vec3::new(0., 0., 1.),
+                    }),
+                );
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Grid medium of {} x {} x {} voxels from {} to {}, phase function: {}",
+            self.grid.nx,
+            self.grid.ny,
+            self.grid.nz,
+            self.min,
+            self.max,
+            self.phase_function.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Unit box with a density gradient along x: empty for x < 0.25, rising linearly to 2 at
+    // x = 0.75 and constant from there. Crossing it along x gives an optical depth of exactly 1.
+    fn gradient_medium() -> GridMedium {
+        GridMedium::new(
+            DensityGrid::new(2, 1, 1, vec![0., 2.]).unwrap(),
+            Point::new(0., 0., 0.),
+            Point::new(1., 1., 1.),
+            0.4,
+            0.6,
+            0.,
+        )
+    }
+
+    fn ray_along_x() -> Ray {
+        Ray::new(Point::new(-1., 0.5, 0.5), Vec3::new(2., 0., 0.))
+    }
+
+    #[test]
+    fn it_estimates_transmittance_with_ratio_tracking() {
+        let medium = gradient_medium();
+
+        let samples = 20000;
+        let total: f32 = (0..samples)
+            .map(|_| medium.transmittance(&ray_along_x(), Interval::new(0.001, 100.)))
+            .sum();
+
+        let expected = (-1f32).exp();
+        let actual = total / samples as f32;
+
+        assert!((actual - expected).abs() < 0.01, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn it_collides_according_to_its_density_with_delta_tracking() {
+        let medium = gradient_medium();
+
+        let samples = 20000;
+        let mut transmitted = 0;
+
+        for _ in 0..samples {
+            match medium.hit_by(&ray_along_x(), Interval::new(0.001, 100.)) {
+                (true, Some(hit)) => assert!(hit.p.x() >= 0.25 && hit.p.x() <= 1.),
+                _ => transmitted += 1,
+            }
+        }
+
+        let expected = (-1f32).exp();
+        let actual = transmitted as f32 / samples as f32;
+
+        assert!((actual - expected).abs() < 0.02, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn it_is_missed_outside_its_box() {
+        let medium = gradient_medium();
+        let ray = Ray::new(Point::new(-1., 2., 0.5), Vec3::new(1., 0., 0.));
+
+        assert!(!medium.hit_by(&ray, Interval::new(0.001, 100.)).0);
+        assert_eq!(medium.transmittance(&ray, Interval::new(0.001, 100.)), 1.);
+    }
+
+    #[test]
+    fn it_handles_an_empty_medium() {
+        let medium = GridMedium::new(
+            DensityGrid::new(2, 1, 1, vec![0., 2.]).unwrap(),
+            Point::new(0., 0., 0.),
+            Point::new(1., 1., 1.),
+            0.,
+            0.,
+            0.,
+        );
+
+        assert!(!medium.hit_by(&ray_along_x(), Interval::new(0.001, 100.)).0);
+        assert_eq!(
+            medium.transmittance(&ray_along_x(), Interval::new(0.001, 100.)),
+            1.
+        );
+
+        let ray = ray_along_x();
+        let hit = Hit::new(&ray, Point::new(0.5, 0.5, 0.5), -ray.direction.unit(), 0.75);
+        let (_, attenuation) = medium.material().scatter(&ray, &hit).unwrap();
+
+        assert!(attenuation.x().is_finite());
+    }
+}